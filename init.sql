@@ -61,4 +61,296 @@ CREATE INDEX IF NOT EXISTS idx_fields_associated_terms_trgm ON standard_fields U
 ALTER TABLE standard_fields ADD CONSTRAINT unique_field_cn_name UNIQUE (field_cn_name);
 
 -- 给标准英文名增加唯一约束
-ALTER TABLE standard_fields ADD CONSTRAINT unique_field_en_name UNIQUE (field_en_name);
\ No newline at end of file
+ALTER TABLE standard_fields ADD CONSTRAINT unique_field_en_name UNIQUE (field_en_name);
+
+-- 任务状态：PENDING(待处理) / APPLIED(已审批并落库) / CLOSED(已关闭)
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'PENDING';
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS resolved_at TIMESTAMP WITH TIME ZONE;
+
+-- 字段废弃标记与替代字段
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS is_deprecated BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS replaced_by INT;
+
+-- 5. 标准代码值 (字段下的枚举取值)
+CREATE TABLE IF NOT EXISTS standard_code_items (
+    id SERIAL PRIMARY KEY,
+    field_id INT NOT NULL REFERENCES standard_fields(id) ON DELETE CASCADE,
+    code_value VARCHAR(100) NOT NULL,           -- 代码值 (如：1)
+    code_name VARCHAR(200) NOT NULL,            -- 代码含义 (如：男)
+    remark TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (field_id, code_value)
+);
+
+-- 6. Webhook 订阅 (向下游系统推送字典变更事件)
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    url TEXT NOT NULL,
+    secret VARCHAR(200) NOT NULL,               -- HMAC-SHA256 签名密钥
+    event_types TEXT[] NOT NULL DEFAULT '{}',   -- 订阅的事件类型，空数组表示全部
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Webhook 投递队列与投递日志：PENDING(待投递/重试中) / SUCCEEDED / FAILED(重试耗尽)
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id BIGSERIAL PRIMARY KEY,
+    subscription_id INT NOT NULL REFERENCES webhook_subscriptions(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING',
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_status_code INT,
+    last_error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    delivered_at TIMESTAMP WITH TIME ZONE
+);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries (status, next_attempt_at);
+
+-- 邮件通知：用户邮箱、通知语言 (zh / en) 与退订标记
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email VARCHAR(255);
+ALTER TABLE users ADD COLUMN IF NOT EXISTS locale VARCHAR(10) NOT NULL DEFAULT 'zh';
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email_opt_out BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- 申请人 (匿名提交时为空)，用于在申请处理后通知本人
+ALTER TABLE notification_tasks ADD COLUMN IF NOT EXISTS requester_id INT REFERENCES users(id) ON DELETE SET NULL;
+
+-- 7. 邮件发件箱：PENDING(待发送/重试中) / SENT / FAILED(重试耗尽或地址无效)
+CREATE TABLE IF NOT EXISTS mail_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    recipient VARCHAR(255) NOT NULL,
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING',
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMP WITH TIME ZONE
+);
+CREATE INDEX IF NOT EXISTS idx_mail_outbox_due ON mail_outbox (status, next_attempt_at);
+
+-- 业务域与更新时间 (用于列表筛选、排序与游标分页)
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS domain VARCHAR(50);
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP WITH TIME ZONE;
+UPDATE standard_word_roots SET updated_at = created_at WHERE updated_at IS NULL;
+ALTER TABLE standard_word_roots ALTER COLUMN updated_at SET DEFAULT CURRENT_TIMESTAMP;
+
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS domain VARCHAR(50);
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP WITH TIME ZONE;
+UPDATE standard_fields SET updated_at = created_at WHERE updated_at IS NULL;
+ALTER TABLE standard_fields ALTER COLUMN updated_at SET DEFAULT CURRENT_TIMESTAMP;
+
+-- 任意更新自动刷新 updated_at
+CREATE OR REPLACE FUNCTION touch_updated_at() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_word_roots_updated_at ON standard_word_roots;
+CREATE TRIGGER trg_word_roots_updated_at BEFORE UPDATE ON standard_word_roots
+    FOR EACH ROW EXECUTE FUNCTION touch_updated_at();
+
+DROP TRIGGER IF EXISTS trg_fields_updated_at ON standard_fields;
+CREATE TRIGGER trg_fields_updated_at BEFORE UPDATE ON standard_fields
+    FOR EACH ROW EXECUTE FUNCTION touch_updated_at();
+
+CREATE INDEX IF NOT EXISTS idx_roots_created_id ON standard_word_roots (created_at, id);
+CREATE INDEX IF NOT EXISTS idx_roots_updated_id ON standard_word_roots (updated_at, id);
+CREATE INDEX IF NOT EXISTS idx_fields_created_id ON standard_fields (created_at, id);
+CREATE INDEX IF NOT EXISTS idx_fields_updated_id ON standard_fields (updated_at, id);
+CREATE INDEX IF NOT EXISTS idx_fields_composition_ids ON standard_fields USING GIN (composition_ids);
+
+-- 拼音索引：全拼与首字母，由应用在写入时与启动时维护（名称及各同义词以空格分隔）
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS cn_pinyin TEXT;
+ALTER TABLE standard_word_roots ADD COLUMN IF NOT EXISTS cn_initials TEXT;
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS cn_pinyin TEXT;
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS cn_initials TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_roots_pinyin_trgm ON standard_word_roots USING GIN (cn_pinyin gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_roots_initials_trgm ON standard_word_roots USING GIN (cn_initials gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_fields_pinyin_trgm ON standard_fields USING GIN (cn_pinyin gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_fields_initials_trgm ON standard_fields USING GIN (cn_initials gin_trgm_ops);
+
+-- 仅刷新拼音索引时不改动 updated_at
+DROP TRIGGER IF EXISTS trg_word_roots_updated_at ON standard_word_roots;
+CREATE TRIGGER trg_word_roots_updated_at BEFORE UPDATE ON standard_word_roots
+    FOR EACH ROW
+    WHEN ((to_jsonb(OLD) - 'cn_pinyin' - 'cn_initials' - 'updated_at') IS DISTINCT FROM (to_jsonb(NEW) - 'cn_pinyin' - 'cn_initials' - 'updated_at'))
+    EXECUTE FUNCTION touch_updated_at();
+
+DROP TRIGGER IF EXISTS trg_fields_updated_at ON standard_fields;
+CREATE TRIGGER trg_fields_updated_at BEFORE UPDATE ON standard_fields
+    FOR EACH ROW
+    WHEN ((to_jsonb(OLD) - 'cn_pinyin' - 'cn_initials' - 'updated_at') IS DISTINCT FROM (to_jsonb(NEW) - 'cn_pinyin' - 'cn_initials' - 'updated_at'))
+    EXECUTE FUNCTION touch_updated_at();
+
+-- 分词停用词：切分后整段命中即忽略（如“的”“及”），由管理员维护
+CREATE TABLE IF NOT EXISTS segment_stop_words (
+    id SERIAL PRIMARY KEY,
+    word VARCHAR(20) NOT NULL UNIQUE,
+    remark TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+-- 只预置无实义的虚词；地、得、对、其 常作词素（出生地、得分、对方、其他），不作停用词
+INSERT INTO segment_stop_words (word) VALUES
+    ('的'), ('之'), ('了'), ('着'), ('及'), ('与'), ('和'), ('或'), ('并')
+ON CONFLICT (word) DO NOTHING;
+
+-- 自定义分词词典：补充短语（词频、词性）与“不拆分 / 强制拆分”规则，变更后实时重建分词器
+CREATE TABLE IF NOT EXISTS segment_dictionary (
+    id SERIAL PRIMARY KEY,
+    word VARCHAR(50) NOT NULL UNIQUE,
+    freq INTEGER,                                  -- 为空时由分词器估算
+    tag VARCHAR(10),                               -- 词性，如 n、nz、vn
+    rule VARCHAR(20) NOT NULL DEFAULT 'normal'
+        CHECK (rule IN ('normal', 'never_split', 'force_split')),
+    remark TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+DROP TRIGGER IF EXISTS trg_segment_dictionary_updated_at ON segment_dictionary;
+CREATE TRIGGER trg_segment_dictionary_updated_at BEFORE UPDATE ON segment_dictionary
+    FOR EACH ROW EXECUTE FUNCTION touch_updated_at();
+
+-- 常用英文缩写：生成词根缩写建议时优先采用（英文单词小写 → 缩写）
+CREATE TABLE IF NOT EXISTS abbreviation_dictionary (
+    id SERIAL PRIMARY KEY,
+    word VARCHAR(50) NOT NULL UNIQUE,
+    abbr VARCHAR(20) NOT NULL,
+    remark TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+INSERT INTO abbreviation_dictionary (word, abbr) VALUES
+    ('account', 'acct'), ('address', 'addr'), ('amount', 'amt'), ('average', 'avg'), ('balance', 'bal'),
+    ('category', 'cat'), ('code', 'cd'), ('count', 'cnt'), ('currency', 'ccy'), ('customer', 'cust'),
+    ('date', 'dt'), ('department', 'dept'), ('description', 'desc'), ('destination', 'dest'), ('employee', 'emp'),
+    ('flag', 'flg'), ('identifier', 'id'), ('indicator', 'ind'), ('information', 'info'), ('management', 'mgmt'),
+    ('maximum', 'max'), ('message', 'msg'), ('minimum', 'min'), ('name', 'nm'), ('number', 'no'),
+    ('order', 'ord'), ('organization', 'org'), ('payment', 'pmt'), ('percentage', 'pct'), ('price', 'prc'),
+    ('product', 'prod'), ('quantity', 'qty'), ('reference', 'ref'), ('sequence', 'seq'), ('source', 'src'),
+    ('status', 'stat'), ('telephone', 'tel'), ('time', 'tm'), ('total', 'tot'), ('transaction', 'txn'),
+    ('type', 'typ'), ('version', 'ver')
+ON CONFLICT (word) DO NOTHING;
+
+-- 词典质量检查报告（手动或定时运行）
+CREATE TABLE IF NOT EXISTS lint_reports (
+    id SERIAL PRIMARY KEY,
+    trigger VARCHAR(20) NOT NULL,                  -- manual / scheduled
+    error_count INTEGER NOT NULL DEFAULT 0,
+    warning_count INTEGER NOT NULL DEFAULT 0,
+    info_count INTEGER NOT NULL DEFAULT 0,
+    findings JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 词根同义词：同一命名空间（词根 domain，未设置为 ''）内一个同义词只能归属一个词根
+-- associated_terms 保留为展示/检索用的冗余列，写入时与本表保持一致
+CREATE TABLE IF NOT EXISTS root_synonyms (
+    id SERIAL PRIMARY KEY,
+    root_id INT NOT NULL REFERENCES standard_word_roots(id) ON DELETE CASCADE,
+    term VARCHAR(100) NOT NULL,
+    namespace VARCHAR(50) NOT NULL DEFAULT '',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (namespace, term)
+);
+CREATE INDEX IF NOT EXISTS idx_root_synonyms_root ON root_synonyms (root_id);
+
+-- 历史数据迁移：冲突的同义词归属 ID 最小的词根，其余词根上的保留在 associated_terms 中，
+-- 通过 GET /synonyms/conflicts 查看并处理
+INSERT INTO root_synonyms (root_id, term, namespace)
+SELECT r.id, t.term, COALESCE(r.domain, '')
+FROM standard_word_roots r,
+     unnest(regexp_split_to_array(COALESCE(r.associated_terms, ''), '[\s,，]+')) AS t(term)
+WHERE t.term <> ''
+ORDER BY r.id
+ON CONFLICT (namespace, term) DO NOTHING;
+
+-- 多语言名称与定义（词根、标准字段、代码值），locale 如 ja、zh-TW；中文名称本身为基准语言，不在此表
+CREATE TABLE IF NOT EXISTS localized_labels (
+    id SERIAL PRIMARY KEY,
+    entity_type VARCHAR(20) NOT NULL CHECK (entity_type IN ('root', 'field', 'code_item')),
+    entity_id INT NOT NULL,
+    locale VARCHAR(35) NOT NULL,
+    label VARCHAR(200) NOT NULL,
+    definition TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (entity_type, entity_id, locale)
+);
+CREATE INDEX IF NOT EXISTS idx_localized_labels_trgm ON localized_labels USING GIN (label gin_trgm_ops);
+
+DROP TRIGGER IF EXISTS trg_localized_labels_updated_at ON localized_labels;
+CREATE TRIGGER trg_localized_labels_updated_at BEFORE UPDATE ON localized_labels
+    FOR EACH ROW EXECUTE FUNCTION touch_updated_at();
+
+-- 实体删除（含 TRUNCATE 清空）时同步删除其多语言名称，TG_ARGV[0] 为实体类型
+CREATE OR REPLACE FUNCTION purge_localized_labels() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'TRUNCATE' THEN
+        DELETE FROM localized_labels WHERE entity_type = TG_ARGV[0];
+    ELSE
+        DELETE FROM localized_labels WHERE entity_type = TG_ARGV[0] AND entity_id = OLD.id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_roots_purge_labels ON standard_word_roots;
+CREATE TRIGGER trg_roots_purge_labels AFTER DELETE ON standard_word_roots
+    FOR EACH ROW EXECUTE FUNCTION purge_localized_labels('root');
+DROP TRIGGER IF EXISTS trg_roots_truncate_labels ON standard_word_roots;
+CREATE TRIGGER trg_roots_truncate_labels AFTER TRUNCATE ON standard_word_roots
+    FOR EACH STATEMENT EXECUTE FUNCTION purge_localized_labels('root');
+DROP TRIGGER IF EXISTS trg_fields_purge_labels ON standard_fields;
+CREATE TRIGGER trg_fields_purge_labels AFTER DELETE ON standard_fields
+    FOR EACH ROW EXECUTE FUNCTION purge_localized_labels('field');
+DROP TRIGGER IF EXISTS trg_fields_truncate_labels ON standard_fields;
+CREATE TRIGGER trg_fields_truncate_labels AFTER TRUNCATE ON standard_fields
+    FOR EACH STATEMENT EXECUTE FUNCTION purge_localized_labels('field');
+DROP TRIGGER IF EXISTS trg_code_items_purge_labels ON standard_code_items;
+CREATE TRIGGER trg_code_items_purge_labels AFTER DELETE ON standard_code_items
+    FOR EACH ROW EXECUTE FUNCTION purge_localized_labels('code_item');
+DROP TRIGGER IF EXISTS trg_code_items_truncate_labels ON standard_code_items;
+CREATE TRIGGER trg_code_items_truncate_labels AFTER TRUNCATE ON standard_code_items
+    FOR EACH STATEMENT EXECUTE FUNCTION purge_localized_labels('code_item');
+
+-- 标准字段治理属性：业务定义、计算口径、示例值、数据所有者、数据管家、来源系统，以及审核记录
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS business_definition TEXT;
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS calculation_rule TEXT;
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS example_values TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS data_owner VARCHAR(100);
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS data_steward VARCHAR(100);
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS source_system VARCHAR(100);
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS approved_by INT REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE standard_fields ADD COLUMN IF NOT EXISTS approved_at TIMESTAMP WITH TIME ZONE;
+CREATE INDEX IF NOT EXISTS idx_fields_definition_trgm ON standard_fields USING GIN (business_definition gin_trgm_ops);
+
+-- 名称类列的 trigram 索引：检索使用 `%` / `<%` 运算符与 ILIKE 过滤，避免全表逐行计算相似度
+CREATE INDEX IF NOT EXISTS idx_roots_cn_name_trgm ON standard_word_roots USING GIN (cn_name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_roots_en_abbr_trgm ON standard_word_roots USING GIN (en_abbr gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_roots_en_full_name_trgm ON standard_word_roots USING GIN (en_full_name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_fields_cn_name_trgm ON standard_fields USING GIN (field_cn_name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_fields_en_name_trgm ON standard_fields USING GIN (field_en_name gin_trgm_ops);
+
+-- 创建/更新时间不允许为空，列表排序与键集分页可直接使用 (created_at, id) / (updated_at, id) 索引
+UPDATE standard_word_roots SET created_at = COALESCE(created_at, updated_at, NOW()) WHERE created_at IS NULL;
+UPDATE standard_word_roots SET updated_at = created_at WHERE updated_at IS NULL;
+ALTER TABLE standard_word_roots ALTER COLUMN created_at SET NOT NULL, ALTER COLUMN updated_at SET NOT NULL;
+UPDATE standard_fields SET created_at = COALESCE(created_at, updated_at, NOW()) WHERE created_at IS NULL;
+UPDATE standard_fields SET updated_at = created_at WHERE updated_at IS NULL;
+ALTER TABLE standard_fields ALTER COLUMN created_at SET NOT NULL, ALTER COLUMN updated_at SET NOT NULL;
+
+-- 多语言定义的子串检索（列表检索词同时匹配名称与定义）
+CREATE INDEX IF NOT EXISTS idx_localized_labels_definition_trgm ON localized_labels USING GIN (definition gin_trgm_ops);
+
+-- 清理删除触发器建立之前遗留的孤立多语言名称（实体已不存在）
+DELETE FROM localized_labels l
+WHERE (l.entity_type = 'root' AND NOT EXISTS (SELECT 1 FROM standard_word_roots r WHERE r.id = l.entity_id))
+   OR (l.entity_type = 'field' AND NOT EXISTS (SELECT 1 FROM standard_fields f WHERE f.id = l.entity_id))
+   OR (l.entity_type = 'code_item' AND NOT EXISTS (SELECT 1 FROM standard_code_items c WHERE c.id = l.entity_id));
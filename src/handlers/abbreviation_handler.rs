@@ -0,0 +1,186 @@
+use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
+use serde::de::{self, IntoDeserializer};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::models::abbreviation::{Abbreviation, CreateAbbreviation};
+use crate::services::abbreviation_service::{self, AbbrOptions, AbbrRule};
+
+/// 校验并规范化（单词与缩写均转小写）
+fn validate(payload: &CreateAbbreviation) -> Result<(String, String), String> {
+    let word = payload.word.trim().to_lowercase();
+    let abbr = payload.abbr.trim().to_lowercase();
+    if word.is_empty() || word.len() > 50 || !word.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("word 须为不超过 50 个字符的单个英文单词".to_string());
+    }
+    let conventional = abbr.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+        && abbr.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    if !conventional || abbr.len() > 20 {
+        return Err("abbr 须以字母开头，只含字母数字，且不超过 20 个字符".to_string());
+    }
+    Ok((word, abbr))
+}
+
+/// 唯一约束冲突返回 409
+fn write_error(e: sqlx::Error) -> axum::response::Response {
+    let is_conflict = e.as_database_error().and_then(|d| d.code()).is_some_and(|code| code == "23505");
+    if is_conflict {
+        (StatusCode::CONFLICT, "该单词已有缩写").into_response()
+    } else {
+        tracing::error!("!!! 常用缩写写入失败: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("写入失败: {}", e)).into_response()
+    }
+}
+
+/// 1. 获取常用缩写表
+pub async fn list_abbreviations(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        Abbreviation,
+        "SELECT id, word, abbr, remark, created_at FROM abbreviation_dictionary ORDER BY word"
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(items) => (StatusCode::OK, Json(items)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 新增常用缩写
+pub async fn create_abbreviation(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateAbbreviation>,
+) -> impl IntoResponse {
+    let (word, abbr) = match validate(&payload) {
+        Ok(v) => v,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+
+    let res = sqlx::query_as!(
+        Abbreviation,
+        "INSERT INTO abbreviation_dictionary (word, abbr, remark) VALUES ($1, $2, $3) RETURNING id, word, abbr, remark, created_at",
+        word,
+        abbr,
+        payload.remark
+    )
+    .fetch_one(&state.db)
+    .await;
+
+    match res {
+        Ok(item) => (StatusCode::CREATED, Json(item)).into_response(),
+        Err(e) => write_error(e),
+    }
+}
+
+/// 3. 修改常用缩写
+pub async fn update_abbreviation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateAbbreviation>,
+) -> impl IntoResponse {
+    let (word, abbr) = match validate(&payload) {
+        Ok(v) => v,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+
+    let res = sqlx::query_as!(
+        Abbreviation,
+        "UPDATE abbreviation_dictionary SET word = $1, abbr = $2, remark = $3 WHERE id = $4
+         RETURNING id, word, abbr, remark, created_at",
+        word,
+        abbr,
+        payload.remark,
+        id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match res {
+        Ok(Some(item)) => (StatusCode::OK, Json(item)).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => write_error(e),
+    }
+}
+
+/// 4. 删除常用缩写
+pub async fn delete_abbreviation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    match sqlx::query!("DELETE FROM abbreviation_dictionary WHERE id = $1", id).execute(&state.db).await {
+        Ok(res) if res.rows_affected() > 0 => StatusCode::NO_CONTENT.into_response(),
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("删除失败: {}", e)).into_response(),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct AbbrQuery {
+    pub cn_name: String,
+    pub en_full_name: String,
+    pub rules: Option<String>,   // 逗号分隔，如 known,drop_vowels；默认全部
+    pub first_n: Option<String>, // 逗号分隔的字母数，默认 3,4
+    pub max_len: Option<usize>,  // 默认 12
+}
+
+/// 解析逗号分隔的规则名
+fn parse_rules(text: &str) -> Result<Vec<AbbrRule>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            AbbrRule::deserialize(s.into_deserializer())
+                .map_err(|_: de::value::Error| {
+                    format!("未知的规则: {}，可选: known, existing_root, drop_vowels, first_n, initials, full_name", s)
+                })
+        })
+        .collect()
+}
+
+/// 5. 缩写建议
+///
+/// 逻辑：按英文全称以多种规则生成缩写候选，检查与已有 en_abbr 的占用及前缀冲突并说明原因，
+/// 同时列出中文名相同的已有词根
+pub async fn suggest_abbreviation(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AbbrQuery>,
+) -> impl IntoResponse {
+    let cn_name = query.cn_name.trim();
+    let en_full_name = query.en_full_name.trim();
+    if cn_name.is_empty() || !en_full_name.chars().any(|c| c.is_ascii_alphabetic()) {
+        return (StatusCode::BAD_REQUEST, "cn_name 与 en_full_name 不能为空").into_response();
+    }
+
+    let mut options = AbbrOptions::default();
+    if let Some(text) = &query.rules {
+        match parse_rules(text) {
+            Ok(rules) if !rules.is_empty() => options.rules = rules,
+            Ok(_) => {}
+            Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+        }
+    }
+    if let Some(text) = &query.first_n {
+        let first_n: Vec<usize> = text.split(',').filter_map(|n| n.trim().parse().ok()).filter(|n| (1..=10).contains(n)).collect();
+        if first_n.is_empty() {
+            return (StatusCode::BAD_REQUEST, "first_n 须为 1~10 的整数，逗号分隔").into_response();
+        }
+        options.first_n = first_n;
+    }
+    if let Some(max_len) = query.max_len {
+        options.max_len = max_len.clamp(2, 30);
+    }
+
+    tracing::info!(">>> 生成缩写建议: cn_name={}, en_full_name={}", cn_name, en_full_name);
+    match abbreviation_service::suggest(&state.db, cn_name, en_full_name, &options).await {
+        Ok(result) => {
+            tracing::info!("<<< 缩写建议完成: 候选数={}, 推荐={:?}", result.candidates.len(), result.recommended);
+            (StatusCode::OK, Json(result)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 缩写建议异常: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use axum::{extract::State, Json, http::StatusCode, response::IntoResponse};
 use std::sync::Arc;
-use crate::{AppState, models::user::{User, Claims}};
+use crate::{AppState, middleware::auth::AuthUser, models::user::{User, Claims, NotificationPrefs}};
 use argon2::{Argon2, PasswordHash, PasswordVerifier, password_hash::{SaltString, PasswordHasher}};
 use jsonwebtoken::{encode, Header, EncodingKey};
 use serde::{Deserialize, Serialize};
@@ -12,6 +12,7 @@ use axum::extract::Path;
 pub struct AuthPayload {
     pub username: String,
     pub password: String,
+    pub email: Option<String>, // 注册时可选，用于接收申请处理结果通知
 }
 
 #[derive(Serialize)]
@@ -25,6 +26,7 @@ pub struct AdminCreateUserPayload {
     pub username: String,
     pub password: String,
     pub role: String,
+    pub email: Option<String>,
 }
 
 /// 用户登录
@@ -37,7 +39,7 @@ pub async fn login(
     // 显式映射字段，确保 password_hash 和 role 非空
     let user = sqlx::query_as!(
         User, 
-        r#"SELECT id, username, password_hash as "password_hash!", role as "role!", created_at FROM users WHERE username = $1"#, 
+        r#"SELECT id, username, password_hash as "password_hash!", role as "role!", email, locale, email_opt_out, created_at FROM users WHERE username = $1"#, 
         payload.username
     )
     .fetch_optional(&state.db)
@@ -86,8 +88,8 @@ pub async fn signup(
         .unwrap_or_default();
 
     let res = sqlx::query!(
-        "INSERT INTO users (username, password_hash, role) VALUES ($1, $2, $3)",
-        payload.username, password_hash, "user"
+        "INSERT INTO users (username, password_hash, role, email) VALUES ($1, $2, $3, NULLIF($4, ''))",
+        payload.username, password_hash, "user", payload.email
     )
     .execute(&state.db)
     .await;
@@ -118,10 +120,11 @@ pub async fn create_user_admin(
         .unwrap_or_default();
 
     let res = sqlx::query!(
-        "INSERT INTO users (username, password_hash, role) VALUES ($1, $2, $3)",
+        "INSERT INTO users (username, password_hash, role, email) VALUES ($1, $2, $3, NULLIF($4, ''))",
         payload.username, 
         password_hash, 
-        payload.role
+        payload.role,
+        payload.email
     )
     .execute(&state.db)
     .await;
@@ -146,7 +149,7 @@ pub async fn list_users(
 
     let result = sqlx::query_as!(
         User,
-        r#"SELECT id, username, password_hash as "password_hash!", role as "role!", created_at FROM users ORDER BY id ASC"#
+        r#"SELECT id, username, password_hash as "password_hash!", role as "role!", email, locale, email_opt_out, created_at FROM users ORDER BY id ASC"#
     )
     .fetch_all(&state.db)
     .await;
@@ -222,4 +225,67 @@ pub async fn delete_user(
             (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
         },
     }
-}
\ No newline at end of file
+}
+
+/// 4. 查看当前用户的邮件通知设置
+pub async fn get_notification_prefs(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        NotificationPrefs,
+        r#"SELECT email, locale as "locale?", email_opt_out as "email_opt_out?" FROM users WHERE id = $1"#,
+        claims.sub
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match res {
+        Ok(Some(prefs)) => (StatusCode::OK, Json(prefs)).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 5. 更新当前用户的邮件通知设置（未提供的项保持不变，email 传空字符串表示清除）
+pub async fn update_notification_prefs(
+    State(state): State<Arc<AppState>>,
+    AuthUser(claims): AuthUser,
+    Json(payload): Json<NotificationPrefs>,
+) -> impl IntoResponse {
+    if let Some(locale) = &payload.locale
+        && !["zh", "en"].contains(&locale.as_str())
+    {
+        return (StatusCode::UNPROCESSABLE_ENTITY, "locale 仅支持 zh / en").into_response();
+    }
+    if let Some(email) = &payload.email
+        && !email.is_empty()
+        && !email.contains('@')
+    {
+        return (StatusCode::UNPROCESSABLE_ENTITY, "email 格式不正确").into_response();
+    }
+
+    tracing::info!(">>> 更新通知设置: user_id={}", claims.sub);
+
+    let res = sqlx::query_as!(
+        NotificationPrefs,
+        r#"UPDATE users SET
+             email = CASE WHEN $1::TEXT IS NULL THEN email ELSE NULLIF($1, '') END,
+             locale = COALESCE($2, locale),
+             email_opt_out = COALESCE($3, email_opt_out)
+           WHERE id = $4
+           RETURNING email, locale as "locale?", email_opt_out as "email_opt_out?""#,
+        payload.email,
+        payload.locale,
+        payload.email_opt_out,
+        claims.sub
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match res {
+        Ok(Some(prefs)) => (StatusCode::OK, Json(prefs)).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
+    }
+}
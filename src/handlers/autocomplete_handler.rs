@@ -0,0 +1,21 @@
+use axum::{extract::Query, Json, http::StatusCode, response::IntoResponse};
+use serde::Deserialize;
+
+use crate::services::autocomplete_service::{self, EntityType};
+
+#[derive(Deserialize)]
+pub struct AutocompleteQuery {
+    pub q: String,
+    #[serde(rename = "type")]
+    pub entity_type: Option<EntityType>, // root / field，缺省两者都返回
+    pub limit: Option<usize>,
+}
+
+/// 1. 输入联想（前缀补全）
+///
+/// 逻辑：直接查询内存前缀索引（中文名、同义词、英文名、拼音），不访问数据库
+pub async fn autocomplete(Query(query): Query<AutocompleteQuery>) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(10).clamp(1, 50);
+    let items = autocomplete_service::complete(&query.q, query.entity_type, limit);
+    (StatusCode::OK, Json(items)).into_response()
+}
@@ -0,0 +1,29 @@
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::AppState;
+
+/// 实时事件推送 (Server-Sent Events)
+///
+/// 管理端订阅后即可在任务提交/处理、词根或字段变更时收到通知，替代轮询 `/tasks/count`。
+/// 若订阅者消费过慢导致事件被丢弃，会收到一条 `lagged` 事件，客户端应据此全量刷新。
+pub async fn stream_events(
+    State(state): State<Arc<AppState>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    tracing::info!(">>> 新的事件订阅连接");
+
+    let stream = BroadcastStream::new(state.events.subscribe()).filter_map(|msg| match msg {
+        Ok(event) => Event::default().event(event.name()).json_data(&event).ok().map(Ok),
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            tracing::warn!("--- 事件订阅者处理过慢，丢弃 {} 条事件", skipped);
+            Some(Ok(Event::default().event("lagged").data(skipped.to_string())))
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
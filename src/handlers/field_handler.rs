@@ -1,20 +1,32 @@
-use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
+use axum::{extract::{State, Path, Query}, Json, http::{HeaderMap, StatusCode}, response::IntoResponse};
 use std::sync::Arc;
 use crate::AppState;
-use crate::models::field::{CreateFieldRequest, StandardField};
+use crate::models::field::{CodeItem, CreateFieldRequest, StandardField};
 use crate::models::word_root::WordRoot;
-use crate::handlers::mapping_handler::SuggestQuery; 
-use crate::handlers::word_root_handler::{PaginationQuery, PaginatedResponse};
-use qdrant_client::qdrant::{SearchPointsBuilder, PointStruct, UpsertPointsBuilder, Value};
-use qdrant_client::qdrant::point_id::PointIdOptions;
+use crate::models::label::{ENTITY_CODE_ITEM, ENTITY_FIELD, ENTITY_ROOT};
+use crate::models::pagination::{PaginatedResponse, PaginationQuery};
+use crate::services::event_service::{ChangeAction, DomainEvent};
+use crate::middleware::auth::AuthUser;
+use crate::services::{catalog_service, governance_service, pinyin_service};
+use crate::services::label_service::{self, LocalePrefs};
+use crate::services::search_service::{self, FusionMode};
+use crate::services::vector_service;
 use qdrant_client::qdrant::{DeletePointsBuilder, Filter};
-use std::collections::HashMap;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct FieldSearchQuery {
+    pub q: String,
+    pub mode: Option<FusionMode>,
+    pub limit: Option<usize>,
+}
 
 /// 1. 创建标准字段
 pub async fn create_field(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<CreateFieldRequest>,
+    Json(mut payload): Json<CreateFieldRequest>,
 ) -> impl IntoResponse {
+    governance_service::normalize(&mut payload.governance);
 
     let existing = sqlx::query!(
         "SELECT id FROM standard_fields WHERE field_cn_name = $1 OR field_en_name = $2 LIMIT 1",
@@ -34,40 +46,26 @@ pub async fn create_field(
     let result = sqlx::query_as!(
         StandardField,
         r#"
-        INSERT INTO standard_fields (field_cn_name, field_en_name, composition_ids, data_type, associated_terms)
-        VALUES ($1, $2, $3::INT[], $4, $5)
+        INSERT INTO standard_fields (field_cn_name, field_en_name, composition_ids, data_type, associated_terms, domain,
+                                     business_definition, calculation_rule, example_values, data_owner, data_steward, source_system)
+        VALUES ($1, $2, $3::INT[], $4, $5, $6, NULLIF($7, ''), NULLIF($8, ''), COALESCE($9, '{}'::TEXT[]),
+                NULLIF($10, ''), NULLIF($11, ''), NULLIF($12, ''))
         RETURNING id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-                  data_type, associated_terms, is_standard as "is_standard!", created_at
+                  data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at
         "#,
         payload.field_cn_name, payload.field_en_name, &payload.composition_ids, 
-        payload.data_type, payload.associated_terms
+        payload.data_type, payload.associated_terms, payload.domain,
+        payload.governance.business_definition, payload.governance.calculation_rule, payload.governance.example_values.as_deref(),
+        payload.governance.data_owner, payload.governance.data_steward, payload.governance.source_system
     )
     .fetch_one(&state.db)
     .await;
 
     match result {
         Ok(field) => {
-            let text_to_embed = format!(
-                "{} {}",
-                field.field_cn_name,
-                field.associated_terms.as_deref().unwrap_or("")
-            );
-
-            let embeddings_res = {
-                let mut model = state.embed_model.lock();
-                model.embed(vec![text_to_embed], None)
-            };
-
-            if let Ok(embeddings) = embeddings_res {
-                // 修复：显式指定 HashMap 的 Value 类型
-                let mut payload_map: HashMap<String, Value> = HashMap::new();
-                payload_map.insert("cn_name".to_string(), field.field_cn_name.clone().into());
-                payload_map.insert("en_name".to_string(), field.field_en_name.clone().into());
-
-                let point = PointStruct::new(field.id as u64, embeddings[0].clone(), payload_map);
-                let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("standard_fields", vec![point])).await;
-                tracing::info!("<<< 向量库同步完成: ID={}", field.id);
-            }
+            vector_service::upsert_field(&state, &field).await;
+            pinyin_service::refresh_fields(&state.db, &[field.id]).await;
+            state.events.publish(DomainEvent::FieldsChanged { action: ChangeAction::Created, ids: vec![field.id] }).await;
 
             (StatusCode::CREATED, Json(field)).into_response()
         },
@@ -82,46 +80,15 @@ pub async fn create_field(
 pub async fn list_fields(
     State(state): State<Arc<AppState>>,
     Query(query): Query<PaginationQuery>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    let page = query.page.unwrap_or(1);
-    let page_size = query.page_size.unwrap_or(20);
-    let offset = (page - 1) * page_size;
-    let search_q = query.q.as_deref().unwrap_or("");
-
-    let total = if search_q.is_empty() {
-        sqlx::query_scalar!("SELECT count(*) FROM standard_fields").fetch_one(&state.db).await.unwrap_or(Some(0)).unwrap_or(0)
-    } else {
-        let pattern = format!("%{}%", search_q);
-        sqlx::query_scalar!(
-            "SELECT count(*) FROM standard_fields WHERE field_cn_name ILIKE $1 OR associated_terms ILIKE $1",
-            pattern
-        ).fetch_one(&state.db).await.unwrap_or(Some(0)).unwrap_or(0)
-    };
-
-    let items_res = if search_q.is_empty() {
-        sqlx::query_as!(
-            StandardField,
-            r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-               data_type, associated_terms, is_standard as "is_standard!", created_at 
-               FROM standard_fields ORDER BY created_at DESC LIMIT $1 OFFSET $2"#,
-            page_size, offset
-        ).fetch_all(&state.db).await
-    } else {
-        let pattern = format!("%{}%", search_q);
-        sqlx::query_as!(
-            StandardField,
-            r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-               data_type, associated_terms, is_standard as "is_standard!", created_at 
-               FROM standard_fields 
-               WHERE field_cn_name ILIKE $1 OR associated_terms ILIKE $1 
-               ORDER BY created_at DESC LIMIT $2 OFFSET $3"#,
-            pattern, page_size, offset
-        ).fetch_all(&state.db).await
-    };
-
-    match items_res {
-        Ok(items) => (StatusCode::OK, Json(PaginatedResponse { items, total })).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("查询列表失败: {}", e)).into_response()
+    match catalog_service::list_fields(&state.db, &query).await {
+        Ok(page) => {
+            let prefs = LocalePrefs::from_headers(&headers);
+            let items = label_service::localize(&state.db, ENTITY_FIELD, page.items, |f| f.item.id, &prefs).await;
+            (StatusCode::OK, Json(PaginatedResponse { items, total: page.total, next_cursor: page.next_cursor })).into_response()
+        }
+        Err((status, msg)) => (status, msg).into_response(),
     }
 }
 
@@ -129,6 +96,7 @@ pub async fn list_fields(
 pub async fn get_field_details(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     let field_row = sqlx::query!(
         r#"SELECT composition_ids FROM standard_fields WHERE id = $1"#,
@@ -149,7 +117,7 @@ pub async fn get_field_details(
                 r#"
                 SELECT 
                     r.id, r.cn_name, r.en_abbr, r.en_full_name, 
-                    r.associated_terms, r.remark, r.created_at
+                    r.associated_terms, r.remark, r.domain, r.created_at, r.updated_at
                 FROM UNNEST($1::INT[]) WITH ORDINALITY AS x(id, ord)
                 JOIN standard_word_roots r ON r.id = x.id
                 ORDER BY x.ord
@@ -160,7 +128,11 @@ pub async fn get_field_details(
             .await;
 
             match roots {
-                Ok(r) => (StatusCode::OK, Json(r)).into_response(),
+                Ok(r) => {
+                    let prefs = LocalePrefs::from_headers(&headers);
+                    let r = label_service::localize(&state.db, ENTITY_ROOT, r, |root| root.id, &prefs).await;
+                    (StatusCode::OK, Json(r)).into_response()
+                }
                 Err(err) => {
                     tracing::error!("解析词根失败: {}", err);
                     (StatusCode::INTERNAL_SERVER_ERROR, "解析详情失败").into_response()
@@ -174,40 +146,62 @@ pub async fn get_field_details(
 
 
 /// 4. 更新标准字段
+///
+/// 治理属性按补丁语义更新：未提交的保持原值，提交空字符串 / 空数组才清空。
 pub async fn update_field(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i32>,
-    Json(payload): Json<CreateFieldRequest>,
+    Json(mut payload): Json<CreateFieldRequest>,
 ) -> impl IntoResponse {
+    governance_service::normalize(&mut payload.governance);
     tracing::info!(">>> 更新标准字段: ID={}", id);
 
+    let mut tx = match state.db.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
+    };
+
     // 修复：显式列出返回字段并指定非空别名，解决 Trait From 报错
     let res = sqlx::query_as!(
         StandardField,
         r#"UPDATE standard_fields SET field_cn_name=$1, field_en_name=$2, composition_ids=$3::INT[], 
-           data_type=$4, associated_terms=$5 WHERE id=$6 
+           data_type=$4, associated_terms=$5, domain=$6,
+           business_definition = NULLIF(COALESCE($7, business_definition), ''),
+           calculation_rule = NULLIF(COALESCE($8, calculation_rule), ''),
+           example_values = COALESCE($9::TEXT[], example_values),
+           data_owner = NULLIF(COALESCE($10, data_owner), ''),
+           data_steward = NULLIF(COALESCE($11, data_steward), ''),
+           source_system = NULLIF(COALESCE($12, source_system), '') WHERE id=$13 
            RETURNING id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-                     data_type, associated_terms, is_standard as "is_standard!", created_at"#,
+                     data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at"#,
         payload.field_cn_name, payload.field_en_name, &payload.composition_ids, 
-        payload.data_type, payload.associated_terms, id
-    ).fetch_one(&state.db).await;
+        payload.data_type, payload.associated_terms, payload.domain,
+        payload.governance.business_definition, payload.governance.calculation_rule, payload.governance.example_values.as_deref(),
+        payload.governance.data_owner, payload.governance.data_steward, payload.governance.source_system, id
+    ).fetch_one(&mut *tx).await;
+
+    // 已审核的标准字段不能清空必填属性
+    let res = match res {
+        Ok(field) if field.is_standard => {
+            let missing = governance_service::missing_attributes(&field);
+            if !missing.is_empty() {
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(serde_json::json!({ "missing_attributes": missing })),
+                )
+                    .into_response();
+            }
+            tx.commit().await.map(|_| field)
+        }
+        Ok(field) => tx.commit().await.map(|_| field),
+        Err(e) => Err(e),
+    };
 
     match res {
         Ok(field) => {
-            let text = format!("{} {}", field.field_cn_name, field.associated_terms.as_deref().unwrap_or(""));
-            let embeddings_res = {
-                let mut model = state.embed_model.lock();
-                model.embed(vec![text], None)
-            };
-
-            if let Ok(embeddings) = embeddings_res {
-                let mut payload_map: HashMap<String, Value> = HashMap::new();
-                payload_map.insert("cn_name".to_string(), field.field_cn_name.clone().into());
-                payload_map.insert("en_name".to_string(), field.field_en_name.clone().into());
-
-                let point = PointStruct::new(field.id as u64, embeddings[0].clone(), payload_map);
-                let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("standard_fields", vec![point])).await;
-            }
+            vector_service::upsert_field(&state, &field).await;
+            pinyin_service::refresh_fields(&state.db, &[field.id]).await;
+            state.events.publish(DomainEvent::FieldsChanged { action: ChangeAction::Updated, ids: vec![field.id] }).await;
             StatusCode::OK.into_response()
         },
         Err(e) => {
@@ -225,6 +219,7 @@ pub async fn delete_field(State(state): State<Arc<AppState>>, Path(id): Path<i32
         Ok(res) => {
             if res.rows_affected() > 0 {
                 let _ = state.qdrant.delete_points(DeletePointsBuilder::new("standard_fields").points(vec![id as u64])).await;
+                state.events.publish(DomainEvent::FieldsChanged { action: ChangeAction::Deleted, ids: vec![id] }).await;
                 StatusCode::NO_CONTENT.into_response()
             } else {
                 StatusCode::NOT_FOUND.into_response()
@@ -235,72 +230,39 @@ pub async fn delete_field(State(state): State<Arc<AppState>>, Path(id): Path<i32
 }
 
 /// 6. 用户端搜索接口
+///
+/// 混合检索：精确/同义词、pg_trgm 与语义检索同时召回，融合排序后返回完整字段及各路得分。
+/// `mode=rrf`（默认）按名次融合，`mode=weighted` 按分数加权。
 pub async fn search_field(
     State(state): State<Arc<AppState>>, 
-    Query(query): Query<SuggestQuery>
+    Query(query): Query<FieldSearchQuery>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    let q_pattern = format!("%{}%", query.q);
-    let sql_results = sqlx::query_as!(
-        StandardField,
-        r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-                  data_type, associated_terms, is_standard as "is_standard!", created_at
-           FROM standard_fields 
-           WHERE field_cn_name ILIKE $1 OR associated_terms ILIKE $1 
-           LIMIT 10"#,
-        q_pattern
-    ).fetch_all(&state.db).await.unwrap_or_default();
-
-    if !sql_results.is_empty() {
-        return Json(sql_results).into_response();
-    }
-
-    let query_vector_res = {
-        let mut model = state.embed_model.lock();
-        model.embed(vec![&query.q], None)
-    };
-
-    if let Ok(embeddings) = query_vector_res {
-        let query_vector = embeddings[0].clone();
-        let search_res = state.qdrant.search_points(
-            SearchPointsBuilder::new("standard_fields", query_vector, 5).with_payload(true)
-        ).await;
-
-        if let Ok(res) = search_res {
-            let fields: Vec<serde_json::Value> = res.result.into_iter().map(|p| {
-                let pay = p.payload;
-                let id_json = match p.id {
-                    Some(pid) => match pid.point_id_options {
-                        Some(PointIdOptions::Num(n)) => serde_json::json!(n),
-                        Some(PointIdOptions::Uuid(u)) => serde_json::json!(u),
-                        None => serde_json::json!(null),
-                    },
-                    None => serde_json::json!(null),
-                };
-
-                serde_json::json!({
-                    "id": id_json,
-                    "field_cn_name": pay.get("cn_name").and_then(|v| v.as_str()),
-                    "field_en_name": pay.get("en_name").and_then(|v| v.as_str()),
-                    "score": p.score
-                })
-            }).collect();
-            return (StatusCode::OK, Json(fields)).into_response();
+    let limit = query.limit.unwrap_or(10).clamp(1, 50);
+    match search_service::hybrid_search_fields(&state, &query.q, query.mode.unwrap_or_default(), limit).await {
+        Ok(hits) => {
+            let prefs = LocalePrefs::from_headers(&headers);
+            let hits = label_service::localize(&state.db, ENTITY_FIELD, hits, |h| h.field.id, &prefs).await;
+            (StatusCode::OK, Json(hits)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 字段检索失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("检索失败: {}", e)).into_response()
         }
     }
-
-    Json(Vec::<StandardField>::new()).into_response()
 }
 
 /// 7. 一键清空所有标准字段
 pub async fn clear_all_fields(
     State(state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
-    let db_res = sqlx::query!("TRUNCATE TABLE standard_fields RESTART IDENTITY")
+    let db_res = sqlx::query!("TRUNCATE TABLE standard_fields RESTART IDENTITY CASCADE")
         .execute(&state.db)
         .await;
 
     match db_res {
         Ok(_) => {
+            state.events.publish(DomainEvent::FieldsChanged { action: ChangeAction::Cleared, ids: vec![] }).await;
             let q_res = state.qdrant.delete_points(
                 DeletePointsBuilder::new("standard_fields")
                     .points(Filter::default()) 
@@ -313,4 +275,120 @@ pub async fn clear_all_fields(
         },
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("数据库清空失败: {}", e)).into_response(),
     }
-}
\ No newline at end of file
+}
+
+/// 8. 获取字段下的标准代码值
+pub async fn list_field_codes(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        CodeItem,
+        "SELECT id, field_id, code_value, code_name, remark, created_at
+         FROM standard_code_items WHERE field_id = $1 ORDER BY code_value",
+        id
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(items) => {
+            let prefs = LocalePrefs::from_headers(&headers);
+            let items = label_service::localize(&state.db, ENTITY_CODE_ITEM, items, |c| c.id, &prefs).await;
+            (StatusCode::OK, Json(items)).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("查询代码值失败: {}", e)).into_response(),
+    }
+}
+
+/// 9. 审核为标准字段：必填属性齐全后才能通过
+///
+/// 在同一事务中锁定字段行再检查与更新，避免检查后被并发修改清空必填属性。
+pub async fn approve_field(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    AuthUser(claims): AuthUser,
+) -> impl IntoResponse {
+    let mut tx = match state.db.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("审核失败: {}", e)).into_response(),
+    };
+    let field = sqlx::query_as!(
+        StandardField,
+        r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!",
+                  data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at
+           FROM standard_fields WHERE id = $1 FOR UPDATE"#,
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await;
+    let field = match field {
+        Ok(Some(f)) => f,
+        Ok(None) => return (StatusCode::NOT_FOUND, "未找到该字段").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    if field.is_deprecated {
+        return (StatusCode::CONFLICT, "字段已废弃，不能审核为标准").into_response();
+    }
+    if field.is_standard {
+        return (StatusCode::OK, Json(field)).into_response();
+    }
+    let missing = governance_service::missing_attributes(&field);
+    if !missing.is_empty() {
+        tracing::warn!("--- 字段 {} 缺少必填属性，审核未通过: {:?}", id, missing);
+        return (StatusCode::UNPROCESSABLE_ENTITY, Json(serde_json::json!({ "missing_attributes": missing }))).into_response();
+    }
+
+    let res = sqlx::query_as!(
+        StandardField,
+        r#"UPDATE standard_fields SET is_standard = true, approved_by = $1, approved_at = NOW()
+           WHERE id = $2
+           RETURNING id, field_cn_name, field_en_name, composition_ids as "composition_ids!",
+                     data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at"#,
+        claims.sub,
+        id
+    )
+    .fetch_one(&mut *tx)
+    .await;
+    let res = match res {
+        Ok(field) => tx.commit().await.map(|_| field),
+        Err(e) => Err(e),
+    };
+
+    match res {
+        Ok(field) => {
+            tracing::info!("<<< 字段 {} 已审核为标准，审核人={}", id, claims.sub);
+            state.events.publish(DomainEvent::FieldsChanged { action: ChangeAction::Updated, ids: vec![id] }).await;
+            (StatusCode::OK, Json(field)).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("审核失败: {}", e)).into_response(),
+    }
+}
+
+/// 10. 撤销标准字段审核，退回草稿
+pub async fn revoke_field(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        StandardField,
+        r#"UPDATE standard_fields SET is_standard = false, approved_by = NULL, approved_at = NULL
+           WHERE id = $1
+           RETURNING id, field_cn_name, field_en_name, composition_ids as "composition_ids!",
+                     data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at"#,
+        id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match res {
+        Ok(Some(field)) => {
+            tracing::info!("<<< 字段 {} 已撤销标准审核", id);
+            state.events.publish(DomainEvent::FieldsChanged { action: ChangeAction::Updated, ids: vec![id] }).await;
+            (StatusCode::OK, Json(field)).into_response()
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, "未找到该字段").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("撤销失败: {}", e)).into_response(),
+    }
+}
@@ -0,0 +1,180 @@
+use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::models::label::{LabelQuery, LabelRecord, LocalizedLabel, UpsertLabel};
+use crate::services::event_service::{ChangeAction, DomainEvent};
+use crate::services::label_service::{self, Owner};
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    pub entity_type: Option<String>,
+    pub locale: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ImportLabels {
+    pub items: Vec<LabelRecord>,
+}
+
+/// 多语言名称变更后通知订阅者（代码值按所属字段通知）
+async fn publish_owners(state: &AppState, owners: &[Owner]) {
+    let mut roots: Vec<i32> = owners.iter().filter_map(|o| match o { Owner::Root(id) => Some(*id), _ => None }).collect();
+    let mut fields: Vec<i32> = owners.iter().filter_map(|o| match o { Owner::Field(id) => Some(*id), _ => None }).collect();
+    roots.sort_unstable();
+    roots.dedup();
+    fields.sort_unstable();
+    fields.dedup();
+    if !roots.is_empty() {
+        state.events.publish(DomainEvent::RootsChanged { action: ChangeAction::Updated, ids: roots }).await;
+    }
+    if !fields.is_empty() {
+        state.events.publish(DomainEvent::FieldsChanged { action: ChangeAction::Updated, ids: fields }).await;
+    }
+}
+
+/// 1. 查询多语言名称（可按实体、语言筛选，q 模糊匹配名称与定义）
+pub async fn list_labels(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LabelQuery>,
+) -> impl IntoResponse {
+    if let Some(Err(msg)) = query.entity_type.as_deref().map(label_service::validate_entity_type) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+    let locale = query.locale.as_deref().and_then(label_service::normalize_locale);
+    let pattern = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()).map(|q| format!("%{}%", q));
+    let limit = query.limit.unwrap_or(200).clamp(1, 1000);
+
+    let res = sqlx::query_as!(
+        LocalizedLabel,
+        "SELECT id, entity_type, entity_id, locale, label, definition, created_at, updated_at
+         FROM localized_labels
+         WHERE ($1::TEXT IS NULL OR entity_type = $1) AND ($2::INT IS NULL OR entity_id = $2)
+           AND ($3::TEXT IS NULL OR locale = $3)
+           AND ($4::TEXT IS NULL OR label ILIKE $4 OR definition ILIKE $4)
+         ORDER BY entity_type, entity_id, locale
+         LIMIT $5",
+        query.entity_type,
+        query.entity_id,
+        locale,
+        pattern,
+        limit
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(labels) => (StatusCode::OK, Json(labels)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 新增或修改实体在某个语言下的名称与定义
+pub async fn upsert_label(
+    State(state): State<Arc<AppState>>,
+    Path((entity_type, entity_id, locale)): Path<(String, i32, String)>,
+    Json(payload): Json<UpsertLabel>,
+) -> impl IntoResponse {
+    if let Err(msg) = label_service::validate_entity_type(&entity_type) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+    let (locale, label) = match label_service::validate(&locale, &payload.label) {
+        Ok(v) => v,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+
+    // 实体行锁与写入在同一事务中，避免写入时实体被并发删除而留下孤立名称
+    let mut tx = match state.db.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let owner = match label_service::owner_of(&mut tx, &entity_type, entity_id).await {
+        Ok(Some(owner)) => owner,
+        Ok(None) => return (StatusCode::NOT_FOUND, format!("{} ID={} 不存在", entity_type, entity_id)).into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+
+    tracing::info!(">>> 保存多语言名称: {} {} [{}] {}", entity_type, entity_id, locale, label);
+    let payload = UpsertLabel { label, definition: payload.definition };
+    let res = match label_service::upsert(&mut tx, &entity_type, entity_id, &locale, &payload).await {
+        Ok(saved) => tx.commit().await.map(|_| saved),
+        Err(e) => Err(e),
+    };
+    match res {
+        Ok(saved) => {
+            publish_owners(&state, &[owner]).await;
+            (StatusCode::OK, Json(saved)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 多语言名称保存失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("保存失败: {}", e)).into_response()
+        }
+    }
+}
+
+/// 3. 删除实体在某个语言下的名称
+pub async fn delete_label(
+    State(state): State<Arc<AppState>>,
+    Path((entity_type, entity_id, locale)): Path<(String, i32, String)>,
+) -> impl IntoResponse {
+    if let Err(msg) = label_service::validate_entity_type(&entity_type) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+    let locale = label_service::normalize_locale(&locale).unwrap_or(locale);
+    let res = sqlx::query!(
+        "DELETE FROM localized_labels WHERE entity_type = $1 AND entity_id = $2 AND locale = $3",
+        entity_type,
+        entity_id,
+        locale
+    )
+    .execute(&state.db)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() > 0 => {
+            let owner = match state.db.acquire().await {
+                Ok(mut conn) => label_service::owner_of(&mut conn, &entity_type, entity_id).await.ok().flatten(),
+                Err(_) => None,
+            };
+            publish_owners(&state, owner.as_slice()).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("删除失败: {}", e)).into_response(),
+    }
+}
+
+/// 4. 导出多语言名称（业务键格式，可直接用于导入）
+pub async fn export_labels(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    if let Some(Err(msg)) = query.entity_type.as_deref().map(label_service::validate_entity_type) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+    let locale = query.locale.as_deref().and_then(label_service::normalize_locale);
+    match label_service::export(&state.db, query.entity_type.as_deref(), locale.as_deref()).await {
+        Ok(items) => (StatusCode::OK, Json(serde_json::json!({ "items": items }))).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("导出失败: {}", e)).into_response(),
+    }
+}
+
+/// 5. 批量导入多语言名称（已存在则覆盖）
+pub async fn import_labels(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ImportLabels>,
+) -> impl IntoResponse {
+    tracing::info!(">>> 导入多语言名称: 总数={}", payload.items.len());
+    match label_service::import(&state.db, &payload.items).await {
+        Ok(result) => {
+            publish_owners(&state, &result.owners).await;
+            tracing::info!("<<< 多语言名称导入完成: 成功={}, 失败={}", result.success_count, result.failure_count);
+            (StatusCode::OK, Json(result)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 多语言名称导入失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("导入失败: {}", e)).into_response()
+        }
+    }
+}
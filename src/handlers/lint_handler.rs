@@ -0,0 +1,75 @@
+use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::models::lint::{LINT_MANUAL, LintReport, LintReportSummary};
+use crate::services::lint_service;
+
+#[derive(Deserialize)]
+pub struct ReportQuery {
+    pub limit: Option<i64>,
+}
+
+/// 1. 立即运行词典检查并保存报告
+pub async fn run_lint(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    tracing::info!(">>> 手动运行词典检查");
+    match lint_service::run_and_store(&state.db, LINT_MANUAL).await {
+        Ok(report) => {
+            tracing::info!(
+                "<<< 词典检查完成: 报告={}, 错误={}, 警告={}, 提示={}",
+                report.id,
+                report.error_count,
+                report.warning_count,
+                report.info_count
+            );
+            (StatusCode::OK, Json(report)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 词典检查失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("检查失败: {}", e)).into_response()
+        }
+    }
+}
+
+/// 2. 历史报告列表（仅概要，按时间倒序）
+pub async fn list_reports(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ReportQuery>,
+) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let res = sqlx::query_as!(
+        LintReportSummary,
+        "SELECT id, trigger, error_count, warning_count, info_count, created_at
+         FROM lint_reports ORDER BY id DESC LIMIT $1",
+        limit
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(reports) => (StatusCode::OK, Json(reports)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 3. 报告详情（含全部检查结果）
+pub async fn get_report(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        LintReport,
+        "SELECT id, trigger, error_count, warning_count, info_count, findings, created_at
+         FROM lint_reports WHERE id = $1",
+        id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match res {
+        Ok(Some(report)) => (StatusCode::OK, Json(report)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "报告不存在").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::models::mail::{MAIL_FAILED, MAIL_PENDING, OutboxMail};
+
+#[derive(Deserialize)]
+pub struct OutboxQuery {
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// 1. 查看邮件发件箱（可按状态过滤）
+pub async fn list_outbox(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<OutboxQuery>,
+) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        OutboxMail,
+        "SELECT id, recipient, subject, body, status, attempts, next_attempt_at, last_error, created_at, sent_at
+         FROM mail_outbox
+         WHERE ($1::TEXT IS NULL OR status = $1)
+         ORDER BY id DESC LIMIT $2",
+        query.status,
+        query.limit.unwrap_or(50).clamp(1, 500)
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(items) => (StatusCode::OK, Json(items)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 手动重发（重置尝试次数并立即排队）
+///
+/// 只重发失败的邮件：已发送的不再重复通知，排队中的可能已被发送任务领取。
+pub async fn retry_mail(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    let res = sqlx::query!(
+        "UPDATE mail_outbox SET status = $1, attempts = 0, next_attempt_at = NOW() WHERE id = $2 AND status = $3",
+        MAIL_PENDING,
+        id,
+        MAIL_FAILED
+    )
+    .execute(&state.db)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() > 0 => StatusCode::ACCEPTED.into_response(),
+        Ok(_) => match sqlx::query_scalar!("SELECT status FROM mail_outbox WHERE id = $1", id).fetch_optional(&state.db).await {
+            Ok(Some(status)) => (StatusCode::CONFLICT, format!("只能重发失败的邮件，当前状态: {}", status)).into_response(),
+            Ok(None) => StatusCode::NOT_FOUND.into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        },
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
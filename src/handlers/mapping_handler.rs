@@ -1,35 +1,35 @@
 use axum::{
     Json,
+    body::Bytes,
     extract::{Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header},
     response::IntoResponse,
 };
-use qdrant_client::qdrant::{SearchPointsBuilder, point_id::PointIdOptions};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::AppState;
-use crate::services::mapping_service;
+use crate::services::mapping_service::SemanticOptions;
+use crate::services::segment_service::{self, Segmenter};
+use crate::services::stopword_service::IgnoredToken;
+use crate::services::{mapping_service, proposal_service, search_service};
 
 #[derive(Deserialize)]
 pub struct SuggestQuery {
     pub q: String,
+    pub segmenter: Option<Segmenter>,    // jieba（默认）/ dict
+    pub semantic_top_k: Option<u64>,     // 每段语义召回数量，默认 SUGGEST_SEMANTIC_TOP_K
+    pub semantic_min_score: Option<f32>, // 语义召回相似度下限，默认 SUGGEST_SEMANTIC_MIN_SCORE
 }
 
 #[derive(Serialize)]
 pub struct SuggestResponseV2 {
     pub segments: Vec<mapping_service::Segment>,
-}
-
-#[derive(Serialize)]
-pub struct RootSuggestion {
-    pub id: String,
-    pub cn_name: String,
-    pub en_abbr: String,
-    pub score: f32,
+    pub ignored: Vec<IgnoredToken>, // 被忽略的停用词与标点
 }
 
 /// 1. 分词建议接口 (管理员生产标准字段的核心工具)
+///
 /// 逻辑：将中文输入利用 JIEBA 切分，并匹配标准词根库（含同义词匹配）
 pub async fn suggest_mapping(
     State(state): State<Arc<AppState>>,
@@ -44,9 +44,16 @@ pub async fn suggest_mapping(
     tracing::info!(">>> 正在为管理员生成分词建议: q='{}'", input);
 
     // 调用 Service 层逻辑
-    let segments = mapping_service::suggest_field_name(&state.db, input).await;
-
-    (StatusCode::OK, Json(SuggestResponseV2 { segments })).into_response()
+    let semantic = SemanticOptions::with(query.semantic_top_k, query.semantic_min_score);
+    match mapping_service::suggest_field_name(&state, input, query.segmenter.unwrap_or_default(), semantic).await {
+        Ok(mapping_service::Suggestion { segments, ignored }) => {
+            (StatusCode::OK, Json(SuggestResponseV2 { segments, ignored })).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 分词建议查询失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
 }
 
 /// 2. 语义相似度搜索词根 (生产辅助)
@@ -61,88 +68,213 @@ pub async fn search_similar_roots(
 
     tracing::info!(">>> 正在检索语义相近词根: q='{}'", input);
 
-    // 步骤 1: 向量化文本。
-    // 使用代码块确保 MutexGuard 在向量化完成后立即释放，不阻塞后续异步操作。
-    let query_vector_res = {
-        let mut model = state.embed_model.lock(); // parking_lot 是同步锁，没有 .await
-        model.embed(vec![input], None)
-    };
+    // 向量命中后回表，返回完整词根（与 SQL 检索结果结构一致）
+    match search_service::similar_roots(&state, input, 5).await {
+        Ok(matches) => {
+            tracing::info!("<<< 语义搜索完成: 召回数量={}", matches.len());
+            (StatusCode::OK, Json(matches)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 语义检索词根异常: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
+        }
+    }
+}
 
-    match query_vector_res {
-        Ok(embeddings) => {
-            let query_vector = embeddings[0].clone();
-            tracing::debug!("--- 向量计算完成，准备检索 Qdrant");
-
-            // 步骤 2: 在 Qdrant 的 word_roots 集合中检索
-            let search_res = state
-                .qdrant
-                .search_points(
-                    SearchPointsBuilder::new("word_roots", query_vector, 5).with_payload(true),
-                )
-                .await;
-
-            match search_res {
-                Ok(res) => {
-                    let suggestions: Vec<RootSuggestion> = res
-                        .result
-                        .into_iter()
-                        .map(|p| {
-                            let pay = p.payload;
-
-                            // 解析 ID
-                            let id_str = match p.id {
-                                Some(pid) => match pid.point_id_options {
-                                    Some(PointIdOptions::Num(n)) => n.to_string(),
-                                    Some(PointIdOptions::Uuid(u)) => u,
-                                    None => "0".to_string(),
-                                },
-                                None => "0".to_string(),
-                            };
-
-                            // 解析 Payload 字段 (修复类型推断)
-                            let cn_name = pay
-                                .get("cn_name")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.as_str()) // 显式转换为 &str
-                                .unwrap_or("")
-                                .to_string();
-
-                            let en_abbr = pay
-                                .get("en_abbr")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.as_str())
-                                .unwrap_or("")
-                                .to_string();
-
-                            RootSuggestion {
-                                id: id_str,
-                                cn_name,
-                                en_abbr,
-                                score: p.score,
-                            }
-                        })
-                        .collect();
-
-                    tracing::info!("<<< 语义搜索完成: 召回数量={}", suggestions.len());
-                    (StatusCode::OK, Json(suggestions)).into_response()
-                }
-                Err(e) => {
-                    tracing::error!("!!! Qdrant 检索词根异常: {}", e);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        format!("向量库检索失败: {}", e),
-                    )
-                        .into_response()
+#[derive(Deserialize)]
+pub struct ResolveNameQuery {
+    pub name: String,
+}
+
+/// 3. 物理名反查中文标准
+///
+/// 逻辑：将 `cust_id` / `custId` 之类的物理名拆成单词，按词根英文缩写/全称识别，
+/// 并返回对应（或最相近）的标准字段
+pub async fn resolve_physical_name(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ResolveNameQuery>,
+) -> impl IntoResponse {
+    let name = query.name.trim();
+    if mapping_service::split_identifier(name).is_empty() {
+        return (StatusCode::BAD_REQUEST, "物理名需包含英文字母或数字").into_response();
+    }
+
+    tracing::info!(">>> 正在反查物理名: name='{}'", name);
+
+    match mapping_service::reverse_map(&state.db, name).await {
+        Ok(mapping) => {
+            tracing::info!(
+                "<<< 反查完成: 未识别单词={}, 字段数={}",
+                mapping.unmatched.len(),
+                mapping.fields.len()
+            );
+            (StatusCode::OK, Json(mapping)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 物理名反查异常: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ProposeQuery {
+    pub q: String,
+    pub segmenter: Option<Segmenter>,
+    pub semantic_top_k: Option<u64>,
+    pub semantic_min_score: Option<f32>,
+    pub limit: Option<usize>,
+}
+
+/// 4. 一键生成字段方案 (管理员)
+///
+/// 逻辑：在分词建议的基础上选出最佳词根组合，生成英文名并按末位词根推断数据类型，
+/// 返回可直接提交给 POST /fields 的方案及按置信度排序的备选
+pub async fn propose_field(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ProposeQuery>,
+) -> impl IntoResponse {
+    let input = query.q.trim();
+    if input.is_empty() {
+        return (StatusCode::BAD_REQUEST, "查询内容不能为空").into_response();
+    }
+    let limit = query.limit.unwrap_or(5).clamp(1, 20);
+
+    tracing::info!(">>> 正在生成字段方案: q='{}'", input);
+    let semantic = SemanticOptions::with(query.semantic_top_k, query.semantic_min_score);
+    match proposal_service::propose_fields(&state, input, query.segmenter.unwrap_or_default(), semantic, limit).await {
+        Ok(result) => {
+            tracing::info!("<<< 字段方案生成完成: 方案数={}, 未识别段={:?}", result.proposals.len(), result.unmatched);
+            (StatusCode::OK, Json(result)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 字段方案生成失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
+
+/// 批量映射单次最多处理的条数
+const BATCH_MAX_ITEMS: usize = 2000;
+
+#[derive(Deserialize)]
+pub struct BatchProposeBody {
+    pub items: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BatchProposeQuery {
+    pub segmenter: Option<Segmenter>,
+    pub semantic_top_k: Option<u64>,
+    pub semantic_min_score: Option<f32>,
+    pub limit: Option<usize>, // 每条输入返回的方案数
+}
+
+/// CSV 表头中可能出现的列名，首行首列命中时跳过
+const CSV_HEADERS: &[&str] = &["name", "field_cn_name", "cn_name", "中文名", "字段中文名", "字段名称"];
+
+/// 读取 CSV 每行的第一列（支持双引号包裹与 `""` 转义），忽略空行与表头
+fn parse_csv_first_column(text: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    for (index, line) in text.trim_start_matches('\u{feff}').lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        let value = if let Some(rest) = line.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = rest.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        value.push('"');
+                        chars.next();
+                    }
+                    '"' => break,
+                    _ => value.push(c),
                 }
             }
+            value
+        } else {
+            line.split(',').next().unwrap_or("").to_string()
+        };
+        let value = value.trim();
+        if value.is_empty() || (index == 0 && CSV_HEADERS.contains(&value.to_lowercase().as_str())) {
+            continue;
+        }
+        values.push(value.to_string());
+    }
+    values
+}
+
+/// 5. 批量映射 (管理员)
+///
+/// 逻辑：请求体为 JSON `{ items: [...] }`，或 `Content-Type: text/csv` 的 CSV（取第一列）；
+/// 按集合查询一次性完成所有输入的匹配，返回每条输入的方案，并标记已有标准字段的输入
+pub async fn batch_propose_fields(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<BatchProposeQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let is_csv = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/csv"));
+
+    let items = if is_csv {
+        match std::str::from_utf8(&body) {
+            Ok(text) => parse_csv_first_column(text),
+            Err(_) => return (StatusCode::BAD_REQUEST, "CSV 需为 UTF-8 编码").into_response(),
         }
+    } else {
+        match serde_json::from_slice::<BatchProposeBody>(&body) {
+            Ok(b) => b.items,
+            Err(e) => return (StatusCode::BAD_REQUEST, format!("请求体格式错误: {}", e)).into_response(),
+        }
+    };
+
+    if items.is_empty() {
+        return (StatusCode::BAD_REQUEST, "没有需要映射的内容").into_response();
+    }
+    if items.len() > BATCH_MAX_ITEMS {
+        return (StatusCode::PAYLOAD_TOO_LARGE, format!("单次最多 {} 条", BATCH_MAX_ITEMS)).into_response();
+    }
+    let limit = query.limit.unwrap_or(3).clamp(1, 10);
+
+    tracing::info!(">>> 开始批量映射: 条数={}, 格式={}", items.len(), if is_csv { "csv" } else { "json" });
+    let semantic = SemanticOptions::with(query.semantic_top_k, query.semantic_min_score);
+    match proposal_service::propose_fields_batch(&state, items, query.segmenter.unwrap_or_default(), semantic, limit).await {
+        Ok(result) => {
+            tracing::info!(
+                "<<< 批量映射完成: 已有={}, 有方案={}, 未解决={}",
+                result.existing,
+                result.proposed,
+                result.unresolved
+            );
+            (StatusCode::OK, Json(result)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 批量映射失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
+
+/// 6. 切分对比 (管理员)
+///
+/// 逻辑：按词根中文名与同义词做最大覆盖切分，标出未覆盖片段，并与 Jieba 的切分结果对比
+pub async fn compare_segmentation(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SuggestQuery>,
+) -> impl IntoResponse {
+    let input = query.q.trim();
+    if input.is_empty() {
+        return (StatusCode::BAD_REQUEST, "查询内容不能为空").into_response();
+    }
+
+    match segment_service::compare(&state.db, input).await {
+        Ok(result) => (StatusCode::OK, Json(result)).into_response(),
         Err(e) => {
-            tracing::error!("!!! 向量模型计算异常: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("向量计算失败: {}", e),
-            )
-                .into_response()
+            tracing::error!("!!! 切分对比失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
         }
     }
 }
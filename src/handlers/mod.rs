@@ -2,4 +2,14 @@ pub mod word_root_handler;
 pub mod mapping_handler;
 pub mod field_handler;
 pub mod auth_handler;
-pub mod task_handler;
\ No newline at end of file
+pub mod task_handler;
+pub mod event_handler;
+pub mod webhook_handler;
+pub mod mail_handler;
+pub mod autocomplete_handler;
+pub mod stop_word_handler;
+pub mod segment_dict_handler;
+pub mod abbreviation_handler;
+pub mod lint_handler;
+pub mod synonym_handler;
+pub mod label_handler;
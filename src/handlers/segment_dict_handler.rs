@@ -0,0 +1,168 @@
+use axum::{extract::{State, Path}, Json, http::StatusCode, response::IntoResponse};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::models::segment_dict::{CreateDictEntry, DictEntry, RULE_NORMAL, RULES};
+use crate::services::event_service::{ChangeAction, DomainEvent};
+use crate::services::tokenizer_service;
+
+/// 校验词条，返回规范化后的 (词, 词性, 规则)
+fn validate_entry(payload: &CreateDictEntry) -> Result<(String, Option<String>, String), String> {
+    let word = payload.word.trim();
+    if word.is_empty() {
+        return Err("word 不能为空".to_string());
+    }
+    if word.chars().count() > 50 || word.chars().any(char::is_whitespace) {
+        return Err("word 不能包含空白，且不超过 50 个字符".to_string());
+    }
+    if payload.freq.is_some_and(|f| f < 1) {
+        return Err("freq 必须为正整数".to_string());
+    }
+    let tag = payload.tag.as_deref().map(str::trim).filter(|t| !t.is_empty());
+    if tag.is_some_and(|t| t.len() > 10 || !t.chars().all(|c| c.is_ascii_alphabetic())) {
+        return Err("tag 须为不超过 10 个字母的词性标记，如 n、nz、vn".to_string());
+    }
+    let rule = payload.rule.as_deref().unwrap_or(RULE_NORMAL);
+    if !RULES.contains(&rule) {
+        return Err(format!("未知的规则: {}，可选: {}", rule, RULES.join(", ")));
+    }
+    Ok((word.to_string(), tag.map(str::to_string), rule.to_string()))
+}
+
+/// 唯一约束冲突返回 409
+fn write_error(e: sqlx::Error) -> axum::response::Response {
+    let is_conflict = e.as_database_error().and_then(|d| d.code()).is_some_and(|code| code == "23505");
+    if is_conflict {
+        (StatusCode::CONFLICT, "该词条已存在").into_response()
+    } else {
+        tracing::error!("!!! 自定义词条写入失败: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("写入失败: {}", e)).into_response()
+    }
+}
+
+/// 词典变更后发布事件，各副本（含本副本）的分词器在后台按事件重建，不占用请求；
+/// 因此接口返回时新词条可能尚未生效
+async fn apply(state: &AppState, action: ChangeAction, id: i32) {
+    state.events.publish(DomainEvent::SegmentDictChanged { action, ids: vec![id] }).await;
+}
+
+/// 1. 获取全部自定义词条
+pub async fn list_entries(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        DictEntry,
+        "SELECT id, word, freq, tag, rule, remark, created_at, updated_at FROM segment_dictionary ORDER BY word"
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(entries) => (StatusCode::OK, Json(entries)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 新增词条（分词器由事件在后台重建，响应返回时可能尚未生效）
+pub async fn create_entry(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateDictEntry>,
+) -> impl IntoResponse {
+    let (word, tag, rule) = match validate_entry(&payload) {
+        Ok(v) => v,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+
+    tracing::info!(">>> 新增自定义词条: word={}, rule={}", word, rule);
+    let res = sqlx::query_as!(
+        DictEntry,
+        r#"INSERT INTO segment_dictionary (word, freq, tag, rule, remark) VALUES ($1, $2, $3, $4, $5)
+           RETURNING id, word, freq, tag, rule, remark, created_at, updated_at"#,
+        word,
+        payload.freq,
+        tag,
+        rule,
+        payload.remark
+    )
+    .fetch_one(&state.db)
+    .await;
+
+    match res {
+        Ok(entry) => {
+            apply(&state, ChangeAction::Created, entry.id).await;
+            (StatusCode::CREATED, Json(entry)).into_response()
+        }
+        Err(e) => write_error(e),
+    }
+}
+
+/// 3. 修改词条
+pub async fn update_entry(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateDictEntry>,
+) -> impl IntoResponse {
+    let (word, tag, rule) = match validate_entry(&payload) {
+        Ok(v) => v,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+
+    let res = sqlx::query_as!(
+        DictEntry,
+        r#"UPDATE segment_dictionary SET word = $1, freq = $2, tag = $3, rule = $4, remark = $5
+           WHERE id = $6
+           RETURNING id, word, freq, tag, rule, remark, created_at, updated_at"#,
+        word,
+        payload.freq,
+        tag,
+        rule,
+        payload.remark,
+        id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match res {
+        Ok(Some(entry)) => {
+            apply(&state, ChangeAction::Updated, entry.id).await;
+            (StatusCode::OK, Json(entry)).into_response()
+        }
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => write_error(e),
+    }
+}
+
+/// 4. 删除词条
+pub async fn delete_entry(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    match sqlx::query!("DELETE FROM segment_dictionary WHERE id = $1", id).execute(&state.db).await {
+        Ok(res) if res.rows_affected() > 0 => {
+            apply(&state, ChangeAction::Deleted, id).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("删除失败: {}", e)).into_response(),
+    }
+}
+
+/// 5. 重载分词词典
+///
+/// 逻辑：按当前词根与自定义词典全量重建本副本的分词器，用于排查切分问题（各副本平时经事件自动同步）
+pub async fn reload_dictionary(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    tracing::info!(">>> 手动重载分词词典");
+    match tokenizer_service::rebuild(&state.db).await {
+        Ok(stats) => {
+            tracing::info!(
+                "<<< 分词词典重载完成: 词根词条={}, 自定义词条={}, 耗时={}ms",
+                stats.root_words,
+                stats.custom_words,
+                stats.elapsed_ms
+            );
+            (StatusCode::OK, Json(stats)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! 分词词典重载失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use axum::{extract::{State, Path}, Json, http::StatusCode, response::IntoResponse};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::models::stop_word::{CreateStopWord, StopWord};
+use crate::services::event_service::{ChangeAction, DomainEvent};
+use crate::services::stopword_service;
+
+/// 校验并规范化停用词
+fn validate_word(payload: &CreateStopWord) -> Result<String, String> {
+    let word = payload.word.trim();
+    if word.is_empty() {
+        return Err("word 不能为空".to_string());
+    }
+    if word.chars().count() > 20 || word.chars().any(char::is_whitespace) {
+        return Err("word 不能包含空白，且不超过 20 个字符".to_string());
+    }
+    Ok(word.to_string())
+}
+
+/// 唯一约束冲突返回 409
+fn write_error(e: sqlx::Error) -> axum::response::Response {
+    let is_conflict = e.as_database_error().and_then(|d| d.code()).is_some_and(|code| code == "23505");
+    if is_conflict {
+        (StatusCode::CONFLICT, "该停用词已存在").into_response()
+    } else {
+        tracing::error!("!!! 停用词写入失败: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("写入失败: {}", e)).into_response()
+    }
+}
+
+/// 1. 获取全部停用词
+pub async fn list_stop_words(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        StopWord,
+        "SELECT id, word, remark, created_at FROM segment_stop_words ORDER BY word"
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(words) => (StatusCode::OK, Json(words)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 新增停用词，立即生效
+pub async fn create_stop_word(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateStopWord>,
+) -> impl IntoResponse {
+    let word = match validate_word(&payload) {
+        Ok(w) => w,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+
+    tracing::info!(">>> 新增停用词: {}", word);
+    let res = sqlx::query_as!(
+        StopWord,
+        "INSERT INTO segment_stop_words (word, remark) VALUES ($1, $2) RETURNING id, word, remark, created_at",
+        word,
+        payload.remark
+    )
+    .fetch_one(&state.db)
+    .await;
+
+    match res {
+        Ok(stop_word) => {
+            stopword_service::refresh(&state.db).await;
+            state.events.publish(DomainEvent::StopWordsChanged { action: ChangeAction::Created, ids: vec![stop_word.id] }).await;
+            (StatusCode::CREATED, Json(stop_word)).into_response()
+        }
+        Err(e) => write_error(e),
+    }
+}
+
+/// 3. 修改停用词
+pub async fn update_stop_word(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateStopWord>,
+) -> impl IntoResponse {
+    let word = match validate_word(&payload) {
+        Ok(w) => w,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+
+    let res = sqlx::query_as!(
+        StopWord,
+        "UPDATE segment_stop_words SET word = $1, remark = $2 WHERE id = $3 RETURNING id, word, remark, created_at",
+        word,
+        payload.remark,
+        id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match res {
+        Ok(Some(stop_word)) => {
+            stopword_service::refresh(&state.db).await;
+            state.events.publish(DomainEvent::StopWordsChanged { action: ChangeAction::Updated, ids: vec![stop_word.id] }).await;
+            (StatusCode::OK, Json(stop_word)).into_response()
+        }
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => write_error(e),
+    }
+}
+
+/// 4. 删除停用词
+pub async fn delete_stop_word(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    match sqlx::query!("DELETE FROM segment_stop_words WHERE id = $1", id).execute(&state.db).await {
+        Ok(res) if res.rows_affected() > 0 => {
+            stopword_service::refresh(&state.db).await;
+            state.events.publish(DomainEvent::StopWordsChanged { action: ChangeAction::Deleted, ids: vec![id] }).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("删除失败: {}", e)).into_response(),
+    }
+}
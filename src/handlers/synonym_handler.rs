@@ -0,0 +1,53 @@
+use axum::{extract::State, Json, http::StatusCode, response::IntoResponse};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::models::synonym::ResolveSynonym;
+use crate::models::word_root::WordRoot;
+use crate::services::event_service::{ChangeAction, DomainEvent};
+use crate::services::{pinyin_service, synonym_service, vector_service};
+
+/// 1. 待处理的同义词冲突
+pub async fn list_conflicts(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    match synonym_service::list_claims(&state.db).await {
+        Ok(claims) => (StatusCode::OK, Json(claims)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 2. 处理冲突：同义词归属到指定词根，并从同一命名空间的其他词根移除
+pub async fn resolve_conflict(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ResolveSynonym>,
+) -> impl IntoResponse {
+    let term = payload.term.trim();
+    if term.is_empty() || term.chars().any(|c| c.is_whitespace() || c == ',' || c == '，') {
+        return (StatusCode::UNPROCESSABLE_ENTITY, "term 不能为空，且不能包含空白或逗号").into_response();
+    }
+
+    tracing::info!(">>> 处理同义词冲突: term={}, root_id={}", term, payload.root_id);
+    let ids = match synonym_service::resolve(&state.db, term, payload.root_id).await {
+        Ok(ids) => ids,
+        Err((status, msg)) => return (status, msg).into_response(),
+    };
+
+    // 同步分词器、向量库与拼音索引
+    let roots = sqlx::query_as!(
+        WordRoot,
+        "SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at
+         FROM standard_word_roots WHERE id = ANY($1) ORDER BY id",
+        &ids
+    )
+    .fetch_all(&state.db)
+    .await
+    .unwrap_or_default();
+
+    for root in &roots {
+        vector_service::upsert_root(&state, root).await;
+    }
+    pinyin_service::refresh_roots(&state.db, &ids).await;
+    state.events.publish(DomainEvent::RootsChanged { action: ChangeAction::Updated, ids }).await;
+
+    tracing::info!("<<< 同义词冲突已处理: {} 个词根受影响", roots.len());
+    (StatusCode::OK, Json(roots)).into_response()
+}
@@ -1,39 +1,88 @@
-use axum::{extract::{State, Path}, Json, http::StatusCode, response::IntoResponse};
+use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
 use std::sync::Arc;
 use crate::AppState;
-use serde::{Deserialize, Serialize};
+use crate::middleware::auth::AuthUser;
+use crate::models::task::{FieldRequestPayload, NotificationTask, TaskRequest, TASK_CLOSED, TASK_PENDING};
+use crate::services::event_service::DomainEvent;
+use crate::services::{search_service, task_service};
+use serde::{de, Deserialize, Deserializer};
+
+/// 提交申请的请求体
+///
+/// 按 `task_type` 选择 `payload` 的结构，解析错误会指明具体缺失或错误的属性；
+/// 未携带 `task_type` 时兼容旧版仅包含 `field_cn_name` 的字段申请
+pub struct CreateTaskPayload(pub TaskRequest);
+
+impl<'de> Deserialize<'de> for CreateTaskPayload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let request = if value.get("task_type").is_some() {
+            serde_json::from_value::<TaskRequest>(value)
+        } else {
+            serde_json::from_value::<FieldRequestPayload>(value).map(TaskRequest::FieldRequest)
+        };
+        request.map(CreateTaskPayload).map_err(de::Error::custom)
+    }
+}
 
 #[derive(Deserialize)]
-pub struct CreateTaskPayload {
-    pub field_cn_name: String,
+pub struct SubmitTaskQuery {
+    /// 用户已确认查重结果，仍坚持提交
+    pub confirm: Option<bool>,
 }
 
-#[derive(Serialize, sqlx::FromRow)]
-pub struct NotificationTask {
-    pub id: i32,
-    pub task_type: String,
-    pub payload: serde_json::Value,
-    pub is_read: bool,
-    pub created_at: chrono::DateTime<chrono::Utc>,
+#[derive(Deserialize)]
+pub struct CloseTasksPayload {
+    pub ids: Vec<i32>,
 }
 
-/// 用户提交新增申请
+/// 用户提交申请（新增字段 / 新增词根 / 修改字段 / 废弃字段 / 新增代码值）
+///
+/// 新增字段申请会先进行查重：若已存在相近的标准字段，返回 409 及候选列表，
+/// 用户确认后携带 `?confirm=true` 重新提交才会入库。
+/// 携带登录 Token 提交时记录申请人，处理完成后邮件通知本人。
 pub async fn submit_task(
     State(state): State<Arc<AppState>>,
+    user: Option<AuthUser>,
+    Query(params): Query<SubmitTaskQuery>,
     Json(payload): Json<CreateTaskPayload>,
 ) -> impl IntoResponse {
-    tracing::info!(">>> 用户提交新字段申请: {}", payload.field_cn_name);
-    
-    let res = sqlx::query!(
-        "INSERT INTO notification_tasks (task_type, payload) VALUES ($1, $2)",
-        "FIELD_REQUEST",
-        serde_json::json!({ "field_cn_name": payload.field_cn_name })
+    let CreateTaskPayload(request) = payload;
+
+    if let Err(msg) = request.validate() {
+        tracing::warn!("--- 申请内容校验未通过: {}", msg);
+        return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response();
+    }
+
+    if let (TaskRequest::FieldRequest(p), false) = (&request, params.confirm.unwrap_or(false)) {
+        let similar = search_service::find_similar_fields(&state, &p.field_cn_name, 5).await;
+        if !similar.is_empty() {
+            tracing::info!("--- 字段申请疑似重复: {}, 候选数={}", p.field_cn_name, similar.len());
+            return (
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({ "requires_confirmation": true, "similar_fields": similar })),
+            )
+                .into_response();
+        }
+    }
+
+    tracing::info!(">>> 用户提交申请: type={}", request.task_type());
+
+    let res = sqlx::query_scalar!(
+        "INSERT INTO notification_tasks (task_type, payload, status, requester_id) VALUES ($1, $2, $3, $4) RETURNING id",
+        request.task_type(),
+        request.payload_json(),
+        TASK_PENDING,
+        user.map(|AuthUser(claims)| claims.sub)
     )
-    .execute(&state.db)
+    .fetch_one(&state.db)
     .await;
 
     match res {
-        Ok(_) => StatusCode::CREATED.into_response(),
+        Ok(id) => {
+            state.events.publish(DomainEvent::TaskSubmitted { task_id: id, task_type: request.task_type().to_string() }).await;
+            (StatusCode::CREATED, Json(serde_json::json!({ "id": id }))).into_response()
+        }
         Err(e) => {
             tracing::error!("!!! 提交申请失败: {}", e);
             (StatusCode::INTERNAL_SERVER_ERROR, "提交失败").into_response()
@@ -45,8 +94,8 @@ pub async fn submit_task(
 pub async fn list_tasks(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     let result = sqlx::query_as!(
         NotificationTask,
-        "SELECT id, task_type, payload, is_read as \"is_read!\", created_at as \"created_at!\" 
-         FROM notification_tasks WHERE is_read = false ORDER BY created_at DESC"
+        r#"SELECT id, task_type, payload as "payload!", status, is_read as "is_read!", requester_id, created_at as "created_at!", resolved_at
+           FROM notification_tasks WHERE is_read = false ORDER BY created_at DESC"#
     )
     .fetch_all(&state.db)
     .await;
@@ -57,17 +106,35 @@ pub async fn list_tasks(State(state): State<Arc<AppState>>) -> impl IntoResponse
     }
 }
 
-/// 管理员标记任务为已处理
+/// 管理员标记任务为已处理（仅待处理的任务，已审批或已关闭的任务返回 409）
 pub async fn complete_task(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i32>,
 ) -> impl IntoResponse {
-    let res = sqlx::query!("UPDATE notification_tasks SET is_read = true WHERE id = $1", id)
-        .execute(&state.db)
-        .await;
+    let res = sqlx::query!(
+        "UPDATE notification_tasks SET is_read = true, status = $1, resolved_at = NOW() WHERE id = $2 AND status = $3",
+        TASK_CLOSED,
+        id,
+        TASK_PENDING
+    )
+    .execute(&state.db)
+    .await;
 
     match res {
-        Ok(_) => StatusCode::OK.into_response(),
+        Ok(r) if r.rows_affected() > 0 => {
+            state.events.publish(DomainEvent::TaskResolved { task_ids: vec![id], status: TASK_CLOSED.to_string() }).await;
+            StatusCode::OK.into_response()
+        }
+        Ok(_) => {
+            let exists = sqlx::query_scalar!("SELECT id FROM notification_tasks WHERE id = $1", id)
+                .fetch_optional(&state.db)
+                .await;
+            match exists {
+                Ok(Some(_)) => (StatusCode::CONFLICT, "任务已处理").into_response(),
+                Ok(None) => (StatusCode::NOT_FOUND, "任务不存在").into_response(),
+                Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+            }
+        }
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
@@ -83,4 +150,56 @@ pub async fn count_unprocessed_tasks(State(state): State<Arc<AppState>>) -> impl
         Ok(count) => Json(serde_json::json!({ "count": count.unwrap_or(0) })).into_response(),
         Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "查询失败").into_response(),
     }
-}
\ No newline at end of file
+}
+
+/// 管理员审批通过并直接落库
+/// 请求体可选，用于补全或修正申请内容（如为字段申请补充英文名与词根组成）
+pub async fn apply_task(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    overrides: Option<Json<serde_json::Value>>,
+) -> impl IntoResponse {
+    match task_service::apply_task(&state, id, overrides.map(|Json(v)| v)).await {
+        Ok(applied) => (StatusCode::OK, Json(applied)).into_response(),
+        Err((status, msg)) => {
+            tracing::warn!("--- 审批落库失败: task_id={}, {}", id, msg);
+            (status, msg).into_response()
+        }
+    }
+}
+
+/// 管理员查看近似的待处理字段申请分组
+pub async fn list_task_clusters(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    match task_service::cluster_pending_field_requests(&state.db).await {
+        Ok(clusters) => Json(clusters).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 管理员批量关闭一组任务（如同一簇内的重复申请）
+pub async fn close_tasks(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CloseTasksPayload>,
+) -> impl IntoResponse {
+    let res = sqlx::query_scalar!(
+        "UPDATE notification_tasks SET is_read = true, status = $1, resolved_at = NOW()
+         WHERE id = ANY($2) AND status = $3
+         RETURNING id",
+        TASK_CLOSED,
+        &payload.ids,
+        TASK_PENDING
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(closed_ids) => {
+            let closed = closed_ids.len();
+            if closed > 0 {
+                state.events.publish(DomainEvent::TaskResolved { task_ids: closed_ids, status: TASK_CLOSED.to_string() }).await;
+            }
+            Json(serde_json::json!({ "closed": closed })).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
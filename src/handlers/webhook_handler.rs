@@ -0,0 +1,209 @@
+use axum::{extract::{State, Path, Query}, Json, http::StatusCode, response::IntoResponse};
+use rand::{Rng, distributions::Alphanumeric};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::models::webhook::{CreateWebhook, CreatedWebhook, DELIVERY_PENDING, WebhookDelivery, WebhookSubscription};
+use crate::services::event_service::DomainEvent;
+use crate::services::webhook_service;
+
+#[derive(Deserialize)]
+pub struct DeliveryQuery {
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// 校验订阅参数，返回规范化后的事件类型列表
+fn validate_webhook(payload: &CreateWebhook) -> Result<Vec<String>, String> {
+    if payload.name.trim().is_empty() {
+        return Err("name 不能为空".to_string());
+    }
+    if !(payload.url.starts_with("http://") || payload.url.starts_with("https://")) {
+        return Err("url 必须以 http:// 或 https:// 开头".to_string());
+    }
+    let event_types = payload.event_types.clone().unwrap_or_default();
+    if let Some(unknown) = event_types.iter().find(|t| !DomainEvent::NAMES.contains(&t.as_str())) {
+        return Err(format!("未知的事件类型: {}，可选: {}", unknown, DomainEvent::NAMES.join(", ")));
+    }
+    Ok(event_types)
+}
+
+fn generate_secret() -> String {
+    rand::thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect()
+}
+
+/// 1. 创建 Webhook 订阅
+pub async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateWebhook>,
+) -> impl IntoResponse {
+    let event_types = match validate_webhook(&payload) {
+        Ok(t) => t,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+    let secret = payload.secret.clone().filter(|s| !s.is_empty()).unwrap_or_else(generate_secret);
+
+    tracing::info!(">>> 创建 Webhook 订阅: name={}, url={}", payload.name, payload.url);
+
+    let res = sqlx::query_as!(
+        WebhookSubscription,
+        r#"INSERT INTO webhook_subscriptions (name, url, secret, event_types, is_active)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id, name, url, secret, event_types, is_active, created_at"#,
+        payload.name,
+        payload.url,
+        secret,
+        &event_types,
+        payload.is_active.unwrap_or(true)
+    )
+    .fetch_one(&state.db)
+    .await;
+
+    match res {
+        Ok(sub) => {
+            let secret = sub.secret.clone();
+            (StatusCode::CREATED, Json(CreatedWebhook { subscription: sub, secret })).into_response()
+        }
+        Err(e) => {
+            tracing::error!("!!! Webhook 订阅创建失败: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("创建失败: {}", e)).into_response()
+        }
+    }
+}
+
+/// 2. 获取全部订阅
+pub async fn list_webhooks(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        WebhookSubscription,
+        "SELECT id, name, url, secret, event_types, is_active, created_at FROM webhook_subscriptions ORDER BY id"
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(subs) => (StatusCode::OK, Json(subs)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 3. 更新订阅（secret 留空则保持不变）
+pub async fn update_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateWebhook>,
+) -> impl IntoResponse {
+    let event_types = match validate_webhook(&payload) {
+        Ok(t) => t,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
+
+    let res = sqlx::query_as!(
+        WebhookSubscription,
+        r#"UPDATE webhook_subscriptions
+           SET name = $1, url = $2, secret = COALESCE(NULLIF($3, ''), secret), event_types = $4, is_active = $5
+           WHERE id = $6
+           RETURNING id, name, url, secret, event_types, is_active, created_at"#,
+        payload.name,
+        payload.url,
+        payload.secret,
+        &event_types,
+        payload.is_active.unwrap_or(true),
+        id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match res {
+        Ok(Some(sub)) => (StatusCode::OK, Json(sub)).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
+    }
+}
+
+/// 4. 删除订阅（同时删除其投递记录）
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    match sqlx::query!("DELETE FROM webhook_subscriptions WHERE id = $1", id).execute(&state.db).await {
+        Ok(res) if res.rows_affected() > 0 => StatusCode::NO_CONTENT.into_response(),
+        Ok(_) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("删除失败: {}", e)).into_response(),
+    }
+}
+
+/// 5. 查看订阅的投递日志
+pub async fn list_deliveries(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(query): Query<DeliveryQuery>,
+) -> impl IntoResponse {
+    let res = sqlx::query_as!(
+        WebhookDelivery,
+        "SELECT id, subscription_id, event_type, payload, status, attempts, next_attempt_at,
+                last_status_code, last_error, created_at, delivered_at
+         FROM webhook_deliveries
+         WHERE subscription_id = $1 AND ($2::TEXT IS NULL OR status = $2)
+         ORDER BY id DESC LIMIT $3",
+        id,
+        query.status,
+        query.limit.unwrap_or(50).clamp(1, 500)
+    )
+    .fetch_all(&state.db)
+    .await;
+
+    match res {
+        Ok(items) => (StatusCode::OK, Json(items)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 6. 测试投递：立即发送一条 ping 事件并返回结果
+pub async fn test_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let sub = sqlx::query_as!(
+        WebhookSubscription,
+        "SELECT id, name, url, secret, event_types, is_active, created_at FROM webhook_subscriptions WHERE id = $1",
+        id
+    )
+    .fetch_optional(&state.db)
+    .await;
+
+    match sub {
+        Ok(Some(sub)) => match webhook_service::test_fire(&state.db, &sub).await {
+            Ok(delivery) => (StatusCode::OK, Json(delivery)).into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("测试投递失败: {}", e)).into_response(),
+        },
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 7. 手动重新投递（重置尝试次数并立即排队）
+///
+/// 仍在排队或已被投递任务领取的记录（PENDING）不能重置，否则会丢失领取租约并重复投递。
+pub async fn retry_delivery(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    let res = sqlx::query!(
+        "UPDATE webhook_deliveries SET status = $1, attempts = 0, next_attempt_at = NOW() WHERE id = $2 AND status <> $1",
+        DELIVERY_PENDING,
+        id
+    )
+    .execute(&state.db)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() > 0 => StatusCode::ACCEPTED.into_response(),
+        Ok(_) => match sqlx::query_scalar!("SELECT id FROM webhook_deliveries WHERE id = $1", id).fetch_optional(&state.db).await {
+            Ok(Some(_)) => (StatusCode::CONFLICT, "投递仍在进行中").into_response(),
+            Ok(None) => StatusCode::NOT_FOUND.into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        },
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
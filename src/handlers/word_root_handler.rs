@@ -1,7 +1,13 @@
-use crate::models::word_root::{CreateWordRoot, WordRoot};
-use crate::{AppState, JIEBA};
+use crate::models::label::{ENTITY_ROOT, LabelInput, UpsertLabel};
+use crate::models::pagination::{PaginatedResponse, PaginationQuery};
+use crate::models::synonym::{SynonymConflict, SynonymPolicy};
+use crate::models::word_root::{CreateWordRoot, WordRoot, normalize_terms};
+use crate::services::event_service::{ChangeAction, DomainEvent};
+use crate::services::label_service::{self, LocalePrefs};
+use crate::services::{catalog_service, pinyin_service, synonym_service, vector_service};
+use crate::AppState;
 use axum::{
-    extract::Path, extract::Query, extract::State, http::StatusCode, response::IntoResponse, Json,
+    extract::Path, extract::Query, extract::State, http::HeaderMap, http::StatusCode, response::IntoResponse, Json,
 };
 use qdrant_client::qdrant::{DeletePointsBuilder, Filter, PointStruct, UpsertPointsBuilder, Value};
 use serde::Serialize;
@@ -19,96 +25,146 @@ pub struct ImportResult {
     pub success_count: usize,
     pub failure_count: usize,
     pub errors: Vec<String>,
+    pub warnings: Vec<String>, // on_conflict=warn 时被剔除的冲突同义词
 }
 
-// 分页与搜索参数结构
 #[derive(serde::Deserialize)]
-pub struct PaginationQuery {
-    pub page: Option<i64>,
-    pub page_size: Option<i64>,
-    pub q: Option<String>,
+pub struct SynonymQuery {
+    pub on_conflict: Option<SynonymPolicy>,
 }
 
-// 分页响应结构
-#[derive(serde::Serialize)]
-pub struct PaginatedResponse<T> {
-    pub items: Vec<T>,
-    pub total: i64,
+// 词根写入结果：冲突同义词被剔除时附带 synonym_conflicts
+#[derive(Serialize)]
+pub struct RootWriteResult {
+    #[serde(flatten)]
+    pub root: WordRoot,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub synonym_conflicts: Vec<SynonymConflict>,
+}
+
+fn conflict_response(conflicts: Vec<SynonymConflict>) -> axum::response::Response {
+    (StatusCode::CONFLICT, Json(serde_json::json!({ "synonym_conflicts": conflicts }))).into_response()
 }
 
-/// 辅助函数：规范化同义词字符串（将各种分隔符统一为空格，压缩多余空格）
-fn normalize_terms(input: Option<String>) -> Option<String> {
-    input.map(|s| {
-        s.replace(',', " ")        // 英文逗号
-         .replace('，', " ")       // 中文逗号
-         .split_whitespace()       // 自动处理多空格
-         .collect::<Vec<_>>()
-         .join(" ")                // 单空格连接
-    })
+/// 校验随词根提交的多语言名称
+fn validate_labels(labels: Option<&[LabelInput]>) -> Result<Vec<(String, UpsertLabel)>, String> {
+    labels
+        .unwrap_or_default()
+        .iter()
+        .map(|l| {
+            let (locale, label) = label_service::validate(&l.locale, &l.label)?;
+            Ok((locale, UpsertLabel { label, definition: l.definition.clone() }))
+        })
+        .collect()
+}
+
+/// 在词根写入的事务内同步同义词归属与多语言名称
+async fn save_root_extras(
+    conn: &mut sqlx::PgConnection,
+    root: &WordRoot,
+    namespace: &str,
+    labels: &[(String, UpsertLabel)],
+) -> Result<(), sqlx::Error> {
+    let kept = synonym_service::split_terms(root.associated_terms.as_deref());
+    synonym_service::assign(conn, root.id, namespace, &kept).await?;
+    for (locale, label) in labels {
+        label_service::upsert(conn, ENTITY_ROOT, root.id, locale, label).await?;
+    }
+    Ok(())
+}
+
+fn describe_conflicts(conflicts: &[SynonymConflict]) -> String {
+    conflicts
+        .iter()
+        .map(|c| format!("「{}」已属于词根 {}({})", c.term, c.cn_name, c.en_abbr))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// 1. 创建单个词根
 pub async fn create_root(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<SynonymQuery>,
     Json(mut payload): Json<CreateWordRoot>,
 ) -> impl IntoResponse {
     // 规范化输入
-    payload.associated_terms = normalize_terms(payload.associated_terms);
+    let terms = synonym_service::split_terms(normalize_terms(payload.associated_terms.take()).as_deref());
+    let ns = synonym_service::namespace(payload.domain.as_deref());
+    let labels = match validate_labels(payload.labels.as_deref()) {
+        Ok(l) => l,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
 
     tracing::info!(">>> 开始创建词根: cn_name={}, en_abbr={}", payload.cn_name, payload.en_abbr);
 
+    let mut tx = match state.db.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("创建失败: {}", e)).into_response(),
+    };
+
+    // 中文名不能是其他词根的同义词（无法剔除，不受 on_conflict 影响）
+    match synonym_service::find_name_conflicts(&mut tx, None, &ns, &payload.cn_name).await {
+        Ok(c) if !c.is_empty() => {
+            tracing::warn!("--- 中文名已是其他词根的同义词，拒绝创建: {}", describe_conflicts(&c));
+            return conflict_response(c);
+        }
+        Ok(_) => {}
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("创建失败: {}", e)).into_response(),
+    }
+
+    // 同一命名空间内同义词唯一
+    let conflicts = match synonym_service::find_conflicts(&mut tx, None, &ns, &terms).await {
+        Ok(c) => c,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("创建失败: {}", e)).into_response(),
+    };
+    if !conflicts.is_empty() && query.on_conflict.unwrap_or_default() == SynonymPolicy::Reject {
+        tracing::warn!("--- 同义词冲突，拒绝创建: {}", describe_conflicts(&conflicts));
+        return conflict_response(conflicts);
+    }
+    payload.associated_terms = synonym_service::accepted_terms(&terms, &conflicts);
+
     let result = sqlx::query_as!(
         WordRoot,
         r#"
-        INSERT INTO standard_word_roots (cn_name, en_abbr, en_full_name, associated_terms, remark)
-        VALUES ($1, $2, $3, $4, $5)
-        RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at
+        INSERT INTO standard_word_roots (cn_name, en_abbr, en_full_name, associated_terms, remark, domain)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at
         "#,
         payload.cn_name,
         payload.en_abbr,
         payload.en_full_name,
         payload.associated_terms,
-        payload.remark
+        payload.remark,
+        payload.domain
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await;
 
-    match result {
+    let result = match result {
         Ok(root) => {
-            // A. 更新分词
-            let mut jieba_write = JIEBA.write().await;
-            jieba_write.add_word(&root.cn_name, Some(99999), None);
-
-            if let Some(terms) = &root.associated_terms {
-            for term in terms.split_whitespace() {
-                jieba_write.add_word(term, Some(99999), None);
+            match save_root_extras(&mut tx, &root, &ns, &labels).await {
+                Ok(()) => tx.commit().await.map(|_| root),
+                Err(e) => Err(e),
             }
         }
+        Err(e) => Err(e),
+    };
 
-            // B. 同步向量库
-            let text_to_embed = format!(
-                "{} {} {}",
-                root.cn_name,
-                root.en_full_name.as_deref().unwrap_or(""),
-                root.associated_terms.as_deref().unwrap_or("")
-            );
-
-            let embeddings_res = {
-                let mut model = state.embed_model.lock();
-                model.embed(vec![text_to_embed], None)
-            };
-
-            if let Ok(embeddings) = embeddings_res {
-                let mut payload_map: HashMap<String, Value> = HashMap::new();
-                payload_map.insert("cn_name".to_string(), root.cn_name.clone().into());
-                payload_map.insert("en_abbr".to_string(), root.en_abbr.clone().into());
+    match result {
+        Ok(root) => {
+            // A. 同步向量库与拼音索引
+            vector_service::upsert_root(&state, &root).await;
+            pinyin_service::refresh_roots(&state.db, &[root.id]).await;
 
-                let point = PointStruct::new(root.id as u64, embeddings[0].clone(), payload_map);
-                let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("word_roots", vec![point])).await;
-            }
+            // B. 通知订阅者（分词器由事件监听在后台同步）
+            state.events.publish(DomainEvent::RootsChanged { action: ChangeAction::Created, ids: vec![root.id] }).await;
 
             tracing::info!("<<< 词根创建成功: ID={}", root.id);
-            (StatusCode::CREATED, Json(root)).into_response()
+            (StatusCode::CREATED, Json(RootWriteResult { root, synonym_conflicts: conflicts })).into_response()
+        }
+        Err(e) if synonym_service::is_claimed(&e) => {
+            tracing::warn!("--- 同义词被并发占用，拒绝创建: {}", e);
+            (StatusCode::CONFLICT, synonym_service::CLAIMED_MESSAGE).into_response()
         }
         Err(e) => {
             tracing::error!("!!! 词根创建失败: {}", e);
@@ -120,12 +176,17 @@ pub async fn create_root(
 /// 2. 批量导入词根 (高性能版)
 pub async fn batch_create_roots(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<SynonymQuery>,
     Json(payload): Json<BatchCreateWordRoot>,
 ) -> impl IntoResponse {
     let total_items = payload.items.len();
+    let policy = query.on_conflict.unwrap_or_default();
     let mut success_count = 0;
     let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut reembed = Vec::new();
     let mut points_to_upsert = Vec::new();
+    let mut created_ids = Vec::new();
 
     tracing::info!(">>> 开始高性能批量导入: 总数={}", total_items);
 
@@ -145,41 +206,76 @@ pub async fn batch_create_roots(
     }
 
     // 批量向量化
-    let all_embeddings = {
-        let mut model = state.embed_model.lock();
-        match model.embed(texts_to_embed, None) {
-            Ok(e) => e,
-            Err(e) => {
-                tracing::error!("!!! 批量向量化失败: {}", e);
-                return (StatusCode::INTERNAL_SERVER_ERROR, "AI模型计算失败").into_response();
-            }
+    let all_embeddings = match vector_service::embed(&state, texts_to_embed).await {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("!!! 批量向量化失败: {}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, "AI模型计算失败").into_response();
         }
     };
 
-    // 循环写入 DB
+    // 循环写入 DB（逐行事务，先写入的行占用的同义词对后续行生效）
     for (index, (item, norm_terms)) in processed_items.into_iter().enumerate() {
-        let res = sqlx::query_as!(
-            WordRoot,
-            r#"
-            INSERT INTO standard_word_roots (cn_name, en_abbr, en_full_name, associated_terms, remark)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at
-            "#,
-            item.cn_name, item.en_abbr, item.en_full_name, norm_terms, item.remark
-        )
-        .fetch_one(&state.db)
+        let terms = synonym_service::split_terms(norm_terms.as_deref());
+        let ns = synonym_service::namespace(item.domain.as_deref());
+        let labels = match validate_labels(item.labels.as_deref()) {
+            Ok(l) => l,
+            Err(msg) => {
+                errors.push(format!("行 {}: [{}] {}", index + 1, item.cn_name, msg));
+                continue;
+            }
+        };
+        let res = async {
+            let mut tx = state.db.begin().await?;
+            let name_conflicts = synonym_service::find_name_conflicts(&mut tx, None, &ns, &item.cn_name).await?;
+            if !name_conflicts.is_empty() {
+                return Ok(Err(name_conflicts));
+            }
+            let conflicts = synonym_service::find_conflicts(&mut tx, None, &ns, &terms).await?;
+            if !conflicts.is_empty() && policy == SynonymPolicy::Reject {
+                return Ok(Err(conflicts));
+            }
+            let root = sqlx::query_as!(
+                WordRoot,
+                r#"
+                INSERT INTO standard_word_roots (cn_name, en_abbr, en_full_name, associated_terms, remark, domain)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at
+                "#,
+                item.cn_name, item.en_abbr, item.en_full_name,
+                synonym_service::accepted_terms(&terms, &conflicts), item.remark, item.domain
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            save_root_extras(&mut tx, &root, &ns, &labels).await?;
+            tx.commit().await?;
+            Ok::<_, sqlx::Error>(Ok((root, conflicts)))
+        }
         .await;
 
         match res {
-            Ok(root) => {
+            Ok(Err(conflicts)) => {
+                errors.push(format!("行 {}: [{}] 同义词冲突: {}", index + 1, item.cn_name, describe_conflicts(&conflicts)));
+            }
+            Ok(Ok((root, conflicts))) => {
                 success_count += 1;
-                JIEBA.write().await.add_word(&root.cn_name, Some(99999), None);
+                created_ids.push(root.id);
+
+                // 剔除了冲突同义词的词根，向量需按实际保存的内容重新计算
+                if !conflicts.is_empty() {
+                    warnings.push(format!("行 {}: [{}] 已剔除冲突同义词: {}", index + 1, item.cn_name, describe_conflicts(&conflicts)));
+                    reembed.push(root);
+                    continue;
+                }
 
                 let mut payload_map: HashMap<String, Value> = HashMap::new();
                 payload_map.insert("cn_name".to_string(), root.cn_name.clone().into());
                 payload_map.insert("en_abbr".to_string(), root.en_abbr.clone().into());
                 points_to_upsert.push(PointStruct::new(root.id as u64, all_embeddings[index].clone(), payload_map));
             },
+            Err(e) if synonym_service::is_claimed(&e) => {
+                errors.push(format!("行 {}: [{}] {}", index + 1, item.cn_name, synonym_service::CLAIMED_MESSAGE));
+            }
             Err(e) => {
                 errors.push(format!("行 {}: [{}] 失败: {}", index + 1, item.cn_name, e));
             }
@@ -190,55 +286,32 @@ pub async fn batch_create_roots(
     if !points_to_upsert.is_empty() {
         let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("word_roots", points_to_upsert)).await;
     }
+    for root in &reembed {
+        vector_service::upsert_root(&state, root).await;
+    }
+
+    if !created_ids.is_empty() {
+        pinyin_service::refresh_roots(&state.db, &created_ids).await;
+        state.events.publish(DomainEvent::RootsChanged { action: ChangeAction::Created, ids: created_ids }).await;
+    }
 
     tracing::info!("<<< 批量处理完成: 成功={}", success_count);
-    (StatusCode::OK, Json(ImportResult { success_count, failure_count: errors.len(), errors })).into_response()
+    (StatusCode::OK, Json(ImportResult { success_count, failure_count: errors.len(), errors, warnings })).into_response()
 }
 
 /// 3. 获取分页词根列表 (增加同义词搜索支持)
 pub async fn list_roots(
     State(state): State<Arc<AppState>>,
     Query(query): Query<PaginationQuery>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    let page = query.page.unwrap_or(1);
-    let page_size = query.page_size.unwrap_or(20);
-    let offset = (page - 1) * page_size;
-    let search_q = query.q.as_deref().unwrap_or("");
-
-    // A. 统计总数 (搜索 cn_name, en_abbr, associated_terms)
-    let total = if search_q.is_empty() {
-        sqlx::query_scalar!("SELECT count(*) FROM standard_word_roots")
-            .fetch_one(&state.db).await.unwrap_or(Some(0)).unwrap_or(0)
-    } else {
-        let pattern = format!("%{}%", search_q);
-        sqlx::query_scalar!(
-            "SELECT count(*) FROM standard_word_roots 
-             WHERE cn_name ILIKE $1 OR en_abbr ILIKE $1 OR associated_terms ILIKE $1", 
-            pattern
-        ).fetch_one(&state.db).await.unwrap_or(Some(0)).unwrap_or(0)
-    };
-
-    // B. 获取分页数据
-    let items_res = if search_q.is_empty() {
-        sqlx::query_as!(
-            WordRoot, 
-            "SELECT * FROM standard_word_roots ORDER BY created_at DESC LIMIT $1 OFFSET $2", 
-            page_size, offset
-        ).fetch_all(&state.db).await
-    } else {
-        let pattern = format!("%{}%", search_q);
-        sqlx::query_as!(
-            WordRoot, 
-            "SELECT * FROM standard_word_roots 
-             WHERE cn_name ILIKE $1 OR en_abbr ILIKE $1 OR associated_terms ILIKE $1 
-             ORDER BY created_at DESC LIMIT $2 OFFSET $3", 
-            pattern, page_size, offset
-        ).fetch_all(&state.db).await
-    };
-
-    match items_res {
-        Ok(items) => (StatusCode::OK, Json(PaginatedResponse { items, total })).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("查询异常: {}", e)).into_response(),
+    match catalog_service::list_roots(&state.db, &query).await {
+        Ok(page) => {
+            let prefs = LocalePrefs::from_headers(&headers);
+            let items = label_service::localize(&state.db, ENTITY_ROOT, page.items, |r| r.item.id, &prefs).await;
+            (StatusCode::OK, Json(PaginatedResponse { items, total: page.total, next_cursor: page.next_cursor })).into_response()
+        }
+        Err((status, msg)) => (status, msg).into_response(),
     }
 }
 
@@ -246,50 +319,78 @@ pub async fn list_roots(
 pub async fn update_root(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    Query(query): Query<SynonymQuery>,
     Json(mut payload): Json<CreateWordRoot>,
 ) -> impl IntoResponse {
-    payload.associated_terms = normalize_terms(payload.associated_terms);
+    let terms = synonym_service::split_terms(normalize_terms(payload.associated_terms.take()).as_deref());
+    let ns = synonym_service::namespace(payload.domain.as_deref());
+    let labels = match validate_labels(payload.labels.as_deref()) {
+        Ok(l) => l,
+        Err(msg) => return (StatusCode::UNPROCESSABLE_ENTITY, msg).into_response(),
+    };
     tracing::info!(">>> 更新词根 ID: {}", id);
 
+    let mut tx = match state.db.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
+    };
+    match synonym_service::find_name_conflicts(&mut tx, Some(id), &ns, &payload.cn_name).await {
+        Ok(c) if !c.is_empty() => {
+            tracing::warn!("--- 中文名已是其他词根的同义词，拒绝更新: {}", describe_conflicts(&c));
+            return conflict_response(c);
+        }
+        Ok(_) => {}
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
+    }
+    let conflicts = match synonym_service::find_conflicts(&mut tx, Some(id), &ns, &terms).await {
+        Ok(c) => c,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("更新失败: {}", e)).into_response(),
+    };
+    if !conflicts.is_empty() && query.on_conflict.unwrap_or_default() == SynonymPolicy::Reject {
+        tracing::warn!("--- 同义词冲突，拒绝更新: {}", describe_conflicts(&conflicts));
+        return conflict_response(conflicts);
+    }
+    payload.associated_terms = synonym_service::accepted_terms(&terms, &conflicts);
+
     let result = sqlx::query_as!(
         WordRoot,
         r#"
         UPDATE standard_word_roots 
-        SET cn_name = $1, en_abbr = $2, en_full_name = $3, associated_terms = $4, remark = $5
-        WHERE id = $6
-        RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at
+        SET cn_name = $1, en_abbr = $2, en_full_name = $3, associated_terms = $4, remark = $5, domain = $6
+        WHERE id = $7
+        RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at
         "#,
         payload.cn_name,
         payload.en_abbr,
         payload.en_full_name,
         payload.associated_terms,
         payload.remark,
+        payload.domain,
         id
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await;
 
-    match result {
+    let result = match result {
         Ok(root) => {
-            let text = format!("{} {} {}", 
-                root.cn_name, 
-                root.en_full_name.as_deref().unwrap_or(""), 
-                root.associated_terms.as_deref().unwrap_or("")
-            );
-            
-            let embeddings_res = {
-                let mut model = state.embed_model.lock();
-                model.embed(vec![text], None)
-            };
-
-            if let Ok(embeddings) = embeddings_res {
-                let mut payload_map: HashMap<String, Value> = HashMap::new();
-                payload_map.insert("cn_name".to_string(), root.cn_name.clone().into());
-                payload_map.insert("en_abbr".to_string(), root.en_abbr.clone().into());
-                let point = PointStruct::new(root.id as u64, embeddings[0].clone(), payload_map);
-                let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("word_roots", vec![point])).await;
+            match save_root_extras(&mut tx, &root, &ns, &labels).await {
+                Ok(()) => tx.commit().await.map(|_| root),
+                Err(e) => Err(e),
             }
-            StatusCode::OK.into_response()
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(root) => {
+            vector_service::upsert_root(&state, &root).await;
+            pinyin_service::refresh_roots(&state.db, &[root.id]).await;
+            state.events.publish(DomainEvent::RootsChanged { action: ChangeAction::Updated, ids: vec![root.id] }).await;
+            (StatusCode::OK, Json(RootWriteResult { root, synonym_conflicts: conflicts })).into_response()
+        }
+        Err(e) if synonym_service::is_claimed(&e) => {
+            tracing::warn!("--- 同义词被并发占用，拒绝更新: {}", e);
+            (StatusCode::CONFLICT, synonym_service::CLAIMED_MESSAGE).into_response()
         }
         Err(e) => {
             tracing::error!("!!! 更新失败: {}", e);
@@ -309,6 +410,7 @@ pub async fn delete_root(
         Ok(res) => {
             if res.rows_affected() > 0 {
                 let _ = state.qdrant.delete_points(DeletePointsBuilder::new("word_roots").points(vec![id as u64])).await;
+                state.events.publish(DomainEvent::RootsChanged { action: ChangeAction::Deleted, ids: vec![id] }).await;
                 StatusCode::NO_CONTENT.into_response()
             } else {
                 StatusCode::NOT_FOUND.into_response()
@@ -321,13 +423,14 @@ pub async fn delete_root(
 /// 6. 一键清空所有词根
 pub async fn clear_all_roots(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     tracing::warn!("⚠️ 执行全量清空词根库");
-    let db_res = sqlx::query!("TRUNCATE TABLE standard_word_roots RESTART IDENTITY").execute(&state.db).await;
+    let db_res = sqlx::query!("TRUNCATE TABLE standard_word_roots RESTART IDENTITY CASCADE").execute(&state.db).await;
 
     match db_res {
         Ok(_) => {
             let _ = state.qdrant.delete_points(DeletePointsBuilder::new("word_roots").points(Filter::default())).await;
+            state.events.publish(DomainEvent::RootsChanged { action: ChangeAction::Cleared, ids: vec![] }).await;
             (StatusCode::OK, "所有词根数据已成功清空").into_response()
         }
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("清空异常: {}", e)).into_response(),
     }
-}
\ No newline at end of file
+}
@@ -18,6 +18,8 @@ use qdrant_client::Qdrant;
 use qdrant_client::qdrant::{
     CreateCollectionBuilder, Distance, PointStruct, UpsertPointsBuilder, VectorParamsBuilder,
 };
+use services::event_service::EventBus;
+use services::vector_service;
 use rand::rngs::OsRng;
 use sqlx::postgres::{PgPool, PgPoolOptions};
 use std::env;
@@ -41,7 +43,8 @@ pub static JIEBA: Lazy<RwLock<Jieba>> = Lazy::new(|| RwLock::new(Jieba::new()));
 pub struct AppState {
     pub db: PgPool,
     pub qdrant: Qdrant,
-    pub embed_model: Mutex<TextEmbedding>, // 使用 Mutex 保证 AI 模型调用的可变引用需求
+    pub embed_model: Arc<Mutex<TextEmbedding>>, // 使用 Mutex 保证 AI 模型调用的可变引用需求，Arc 便于移入阻塞线程
+    pub events: EventBus,                  // 领域事件总线 (SSE 推送)
 }
 
 /// 健康检查 Handler：用于运维平台监测服务可用性
@@ -96,7 +99,7 @@ async fn sync_roots_to_qdrant(state: &AppState) {
     tracing::info!("正在同步 [标准词根] 向量到 Qdrant...");
     let roots = sqlx::query_as!(
         crate::models::word_root::WordRoot,
-        "SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at FROM standard_word_roots"
+        "SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at FROM standard_word_roots"
     )
     .fetch_all(&state.db)
     .await
@@ -106,31 +109,19 @@ async fn sync_roots_to_qdrant(state: &AppState) {
         return;
     }
 
-    let mut points = Vec::new();
-    // 修复点：声明为 mut model
-    let mut model = state.embed_model.lock();
-
-    for root in &roots {
-        let text = format!(
-            "{} {} {}",
-            root.cn_name,
-            root.en_full_name.as_deref().unwrap_or(""),
-            root.associated_terms.as_deref().unwrap_or("")
-        );
-
-        if let Ok(embeddings) = model.embed(vec![text], None) {
-            let mut payload: std::collections::HashMap<String, qdrant_client::qdrant::Value> =
-                std::collections::HashMap::new();
-            payload.insert("cn_name".to_string(), root.cn_name.clone().into());
-            payload.insert("en_abbr".to_string(), root.en_abbr.clone().into());
-
-            points.push(PointStruct::new(
-                root.id as u64,
-                embeddings[0].clone(),
-                payload,
-            ));
+    // 模型推理在阻塞线程池中批量完成
+    let texts = roots.iter().map(vector_service::root_embed_text).collect();
+    let points: Vec<PointStruct> = match vector_service::embed(state, texts).await {
+        Ok(embeddings) => roots
+            .iter()
+            .zip(embeddings)
+            .map(|(root, vector)| PointStruct::new(root.id as u64, vector, vector_service::root_payload(root)))
+            .collect(),
+        Err(e) => {
+            tracing::error!("!!! 词根向量同步失败: {}", e);
+            return;
         }
-    }
+    };
 
     if !points.is_empty() {
         let _ = state
@@ -147,7 +138,7 @@ async fn sync_fields_to_qdrant(state: &AppState) {
     let fields = sqlx::query_as!(
         crate::models::field::StandardField,
         r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!", 
-           data_type, associated_terms, is_standard as "is_standard!", created_at FROM standard_fields"#
+           data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at FROM standard_fields"#
     )
     .fetch_all(&state.db)
     .await
@@ -157,30 +148,19 @@ async fn sync_fields_to_qdrant(state: &AppState) {
         return;
     }
 
-    let mut points = Vec::new();
-    // 修复点：声明为 mut model
-    let mut model = state.embed_model.lock();
-
-    for field in &fields {
-        let text = format!(
-            "{} {}",
-            field.field_cn_name,
-            field.associated_terms.as_deref().unwrap_or("")
-        );
-
-        if let Ok(embeddings) = model.embed(vec![text], None) {
-            let mut payload: std::collections::HashMap<String, qdrant_client::qdrant::Value> =
-                std::collections::HashMap::new();
-            payload.insert("cn_name".to_string(), field.field_cn_name.clone().into());
-            payload.insert("en_name".to_string(), field.field_en_name.clone().into());
-
-            points.push(PointStruct::new(
-                field.id as u64,
-                embeddings[0].clone(),
-                payload,
-            ));
+    // 模型推理在阻塞线程池中批量完成
+    let texts = fields.iter().map(vector_service::field_embed_text).collect();
+    let points: Vec<PointStruct> = match vector_service::embed(state, texts).await {
+        Ok(embeddings) => fields
+            .iter()
+            .zip(embeddings)
+            .map(|(field, vector)| PointStruct::new(field.id as u64, vector, vector_service::field_payload(field)))
+            .collect(),
+        Err(e) => {
+            tracing::error!("!!! 标准字段向量同步失败: {}", e);
+            return;
         }
-    }
+    };
 
     if !points.is_empty() {
         let _ = state
@@ -203,32 +183,19 @@ async fn init_qdrant_collections(qdrant: &Qdrant) {
                         .vectors_config(VectorParamsBuilder::new(384, Distance::Cosine)),
                 )
                 .await
-                .expect(&format!("无法创建 Qdrant 集合: {}", name));
+                .unwrap_or_else(|e| panic!("无法创建 Qdrant 集合: {}: {}", name, e));
         }
     }
 }
 
-async fn init_custom_dictionary(pool: &PgPool) {
-    tracing::info!("正在加载分词库自定义词典...");
-    let roots = sqlx::query!("SELECT cn_name, associated_terms FROM standard_word_roots")
-        .fetch_all(pool)
-        .await
-        .unwrap_or_default();
-
-    let mut jieba_write = JIEBA.write().await;
-    for r in &roots {
-        jieba_write.add_word(&r.cn_name, Some(99999), None);
-
-        // 2. 加入同义词（如果有的话）
-        if let Some(terms) = &r.associated_terms {
-            // 假设同义词是用空格分隔的（根据你之前的规范化逻辑）
-            for term in terms.split_whitespace() {
-                jieba_write.add_word(term, Some(99999), None);
-                tracing::debug!("添加同义词到分词器: {}", term);
-            }
-        }
+/// 启动时补齐拼音索引（新增列、拼音规则升级或绕过接口直接改库的数据）
+async fn init_pinyin_index(pool: &PgPool) {
+    let roots = services::pinyin_service::sync_roots(pool, None).await;
+    let fields = services::pinyin_service::sync_fields(pool, None).await;
+    match (roots, fields) {
+        (Ok(r), Ok(f)) => tracing::info!("拼音索引检查完成: 词根更新 {} 条, 字段更新 {} 条", r, f),
+        (Err(e), _) | (_, Err(e)) => tracing::warn!("--- 拼音索引初始化失败: {}", e),
     }
-    tracing::info!("自定义词典加载完成，共计 {} 个词条", roots.len());
 }
 
 #[tokio::main]
@@ -255,7 +222,20 @@ async fn main() {
 
     // 3. 执行启动预热逻辑
     ensure_default_admin(&pool).await;
-    init_custom_dictionary(&pool).await;
+    tracing::info!("正在加载分词库自定义词典...");
+    match services::tokenizer_service::rebuild(&pool).await {
+        Ok(stats) => tracing::info!(
+            "自定义词典加载完成，词根词条 {} 个，自定义词条 {} 个",
+            stats.root_words,
+            stats.custom_words
+        ),
+        Err(e) => tracing::error!("!!! 自定义词典加载失败: {}", e),
+    }
+    init_pinyin_index(&pool).await;
+    match services::stopword_service::reload(&pool).await {
+        Ok(n) => tracing::info!("停用词加载完成，共计 {} 个", n),
+        Err(e) => tracing::error!("!!! 停用词加载失败: {}", e),
+    }
 
     // 4. 初始化 Embedding 模型与向量库
     let current_dir = env::current_dir().expect("Failed to get current dir");
@@ -279,11 +259,30 @@ async fn main() {
     })
     .expect("离线模型加载失败");
 
+    // 多副本部署时开启 EVENTS_PG_NOTIFY，通过 Postgres LISTEN/NOTIFY 同步事件
+    let pg_notify = std::env::var("EVENTS_PG_NOTIFY").is_ok_and(|v| v == "true");
+    let events = EventBus::new(pool.clone(), pg_notify);
+
     let shared_state = Arc::new(AppState {
         db: pool,
         qdrant,
-        embed_model: Mutex::new(model), // 使用高效同步锁
+        embed_model: Arc::new(Mutex::new(model)), // 使用高效同步锁
+        events,
     });
+    shared_state.events.spawn_pg_listener();
+
+    // 输入联想索引：启动时全量构建，之后随词根/字段变更事件增量更新
+    if let Err(e) = services::autocomplete_service::rebuild(&shared_state.db).await {
+        tracing::error!("!!! 补全索引构建失败: {}", e);
+    }
+    services::autocomplete_service::spawn_listener(&shared_state);
+    services::stopword_service::spawn_listener(&shared_state);
+    services::tokenizer_service::spawn_listener(&shared_state);
+    services::webhook_service::spawn_enqueuer(&shared_state);
+    services::webhook_service::spawn_worker(shared_state.db.clone());
+    services::mail_service::spawn_enqueuer(&shared_state);
+    services::mail_service::spawn_worker(shared_state.db.clone());
+    services::lint_service::spawn_worker(shared_state.db.clone());
 
     // 5. 执行向量数据冷启动同步
     sync_roots_to_qdrant(&shared_state).await;
@@ -298,7 +297,12 @@ async fn main() {
     // 7. 定义路由
     let auth_routes = Router::new()
         .route("/signup", post(handlers::auth_handler::signup))
-        .route("/login", post(handlers::auth_handler::login));
+        .route("/login", post(handlers::auth_handler::login))
+        .route(
+            "/me/notifications",
+            get(handlers::auth_handler::get_notification_prefs)
+                .put(handlers::auth_handler::update_notification_prefs),
+        );
 
     let public_routes = Router::new()
         .route("/health", get(health_check)) // 增加监控接口
@@ -307,7 +311,12 @@ async fn main() {
         .route(
             "/similar-roots",
             get(handlers::mapping_handler::search_similar_roots),
-        );
+        )
+        .route(
+            "/resolve-name",
+            get(handlers::mapping_handler::resolve_physical_name),
+        )
+        .route("/autocomplete", get(handlers::autocomplete_handler::autocomplete));
 
     let admin_routes = Router::new()
         .route(
@@ -319,6 +328,10 @@ async fn main() {
             "/roots/batch",
             post(handlers::word_root_handler::batch_create_roots),
         )
+        .route(
+            "/roots/suggest-abbr",
+            get(handlers::abbreviation_handler::suggest_abbreviation),
+        )
         .route(
             "/roots/clear",
             delete(handlers::word_root_handler::clear_all_roots),
@@ -342,6 +355,12 @@ async fn main() {
                 .put(handlers::field_handler::update_field)
                 .delete(handlers::field_handler::delete_field),
         )
+        .route("/fields/:id/approve", post(handlers::field_handler::approve_field))
+        .route("/fields/:id/revoke", post(handlers::field_handler::revoke_field))
+        .route(
+            "/fields/:id/codes",
+            get(handlers::field_handler::list_field_codes),
+        )
         .route(
             "/users",
             post(handlers::auth_handler::create_user_admin).get(handlers::auth_handler::list_users),
@@ -352,12 +371,92 @@ async fn main() {
                 .delete(handlers::auth_handler::delete_user),
         )
         .route("/suggest", get(handlers::mapping_handler::suggest_mapping))
+        .route("/segment", get(handlers::mapping_handler::compare_segmentation))
+        .route("/propose-field", get(handlers::mapping_handler::propose_field))
+        .route("/propose-field/batch", post(handlers::mapping_handler::batch_propose_fields))
+        .route(
+            "/abbreviations",
+            post(handlers::abbreviation_handler::create_abbreviation)
+                .get(handlers::abbreviation_handler::list_abbreviations),
+        )
+        .route(
+            "/abbreviations/:id",
+            put(handlers::abbreviation_handler::update_abbreviation)
+                .delete(handlers::abbreviation_handler::delete_abbreviation),
+        )
+        .route(
+            "/segment-dict",
+            post(handlers::segment_dict_handler::create_entry)
+                .get(handlers::segment_dict_handler::list_entries),
+        )
+        .route(
+            "/segment-dict/reload",
+            post(handlers::segment_dict_handler::reload_dictionary),
+        )
+        .route(
+            "/segment-dict/:id",
+            put(handlers::segment_dict_handler::update_entry)
+                .delete(handlers::segment_dict_handler::delete_entry),
+        )
+        .route("/labels", get(handlers::label_handler::list_labels))
+        .route("/labels/export", get(handlers::label_handler::export_labels))
+        .route("/labels/import", post(handlers::label_handler::import_labels))
+        .route(
+            "/labels/:entity_type/:entity_id/:locale",
+            put(handlers::label_handler::upsert_label).delete(handlers::label_handler::delete_label),
+        )
+        .route("/synonyms/conflicts", get(handlers::synonym_handler::list_conflicts))
+        .route("/synonyms/resolve", post(handlers::synonym_handler::resolve_conflict))
+        .route(
+            "/stop-words",
+            post(handlers::stop_word_handler::create_stop_word)
+                .get(handlers::stop_word_handler::list_stop_words),
+        )
+        .route(
+            "/stop-words/:id",
+            put(handlers::stop_word_handler::update_stop_word)
+                .delete(handlers::stop_word_handler::delete_stop_word),
+        )
+        .route("/lint/run", post(handlers::lint_handler::run_lint))
+        .route("/lint/reports", get(handlers::lint_handler::list_reports))
+        .route("/lint/reports/:id", get(handlers::lint_handler::get_report))
         .route("/tasks", get(handlers::task_handler::list_tasks))
         .route(
             "/tasks/count",
             get(handlers::task_handler::count_unprocessed_tasks),
         )
+        .route(
+            "/tasks/clusters",
+            get(handlers::task_handler::list_task_clusters),
+        )
+        .route("/tasks/close", put(handlers::task_handler::close_tasks))
         .route("/tasks/:id", put(handlers::task_handler::complete_task))
+        .route("/tasks/:id/apply", post(handlers::task_handler::apply_task))
+        .route("/events", get(handlers::event_handler::stream_events))
+        .route(
+            "/webhooks",
+            post(handlers::webhook_handler::create_webhook)
+                .get(handlers::webhook_handler::list_webhooks),
+        )
+        .route(
+            "/webhooks/:id",
+            put(handlers::webhook_handler::update_webhook)
+                .delete(handlers::webhook_handler::delete_webhook),
+        )
+        .route(
+            "/webhooks/:id/deliveries",
+            get(handlers::webhook_handler::list_deliveries),
+        )
+        .route(
+            "/webhooks/:id/test",
+            post(handlers::webhook_handler::test_webhook),
+        )
+        .route(
+            "/webhooks/deliveries/:id/retry",
+            post(handlers::webhook_handler::retry_delivery),
+        )
+        .route("/mails", get(handlers::mail_handler::list_outbox))
+        .route("/mails/:id/retry", post(handlers::mail_handler::retry_mail))
         .layer(axum::middleware::from_fn_with_state(
             shared_state.clone(),
             middleware::auth::guard,
@@ -1,7 +1,8 @@
 use axum::{
+    async_trait,
     body::Body,
-    extract::State,
-    http::{Request, StatusCode, header},
+    extract::{FromRequestParts, State},
+    http::{Request, StatusCode, header, request::Parts},
     middleware::Next,
     response::Response,
 };
@@ -10,39 +11,76 @@ use crate::AppState;
 use crate::models::user::Claims;
 use jsonwebtoken::{decode, DecodingKey, Validation};
 
+/// 解码并校验 JWT
+pub fn decode_claims(token: &str) -> Option<Claims> {
+    decode::<Claims>(
+        token,
+        &DecodingKey::from_secret("secret_key".as_ref()),
+        &Validation::default(),
+    )
+    .ok()
+    .map(|data| data.claims)
+}
+
+/// 已登录用户（任意角色），用于需要识别当前用户的非管理接口
+pub struct AuthUser(pub Claims);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthUser {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.headers
+            .get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .and_then(decode_claims)
+            .map(AuthUser)
+            .ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
+
+/// SSE 订阅路由（嵌套在 `/api/admin` 下，中间件看到的路径已去除前缀）
+const EVENTS_PATH: &str = "/events";
+
+/// 从查询参数中读取 `token`，仅 SSE 订阅路由接受
+///
+/// 其他接口只认 Bearer Token，避免 JWT 出现在 URL 中被访问日志记录
+fn query_token(req: &Request<Body>) -> Option<&str> {
+    if req.uri().path() != EVENTS_PATH {
+        return None;
+    }
+    req.uri()
+        .query()?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+}
+
 /// 管理员权限守卫
 pub async fn guard(
     State(_state): State<Arc<AppState>>,
     req: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // 1. 提取 Authorization Header
-    let auth_header = req.headers()
+    // 1. 提取 Authorization Header，并检查是否为 Bearer Token
+    //    浏览器 EventSource 无法设置请求头，因此仅 SSE 订阅允许通过 `?token=` 查询参数传递
+    let token = req.headers()
         .get(header::AUTHORIZATION)
-        .and_then(|h| h.to_str().ok());
-
-    if let Some(auth_header) = auth_header {
-        // 2. 检查是否为 Bearer Token
-        if auth_header.starts_with("Bearer ") {
-            let token = &auth_header[7..];
-            
-            // 3. 解码并验证 JWT
-            let token_data = decode::<Claims>(
-                token,
-                &DecodingKey::from_secret("secret_key".as_ref()),
-                &Validation::default(),
-            );
-
-            if let Ok(data) = token_data {
-                // 4. 只有角色为 admin 的用户才允许访问管理接口
-                if data.claims.role == "admin" {
-                    return Ok(next.run(req).await);
-                }
-                return Err(StatusCode::FORBIDDEN); // 权限不足
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .or_else(|| query_token(&req));
+
+    if let Some(token) = token {
+        // 2. 解码并验证 JWT
+        if let Some(claims) = decode_claims(token) {
+            // 3. 只有角色为 admin 的用户才允许访问管理接口
+            if claims.role == "admin" {
+                return Ok(next.run(req).await);
             }
+            return Err(StatusCode::FORBIDDEN); // 权限不足
         }
     }
-    
-    // 5. 未提供 Token 或 Token 无效
+
+    // 4. 未提供 Token 或 Token 无效
     Err(StatusCode::UNAUTHORIZED)
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Abbreviation {
+    pub id: i32,
+    pub word: String, // 英文单词（小写）
+    pub abbr: String,
+    pub remark: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateAbbreviation {
+    pub word: String,
+    pub abbr: String,
+    pub remark: Option<String>,
+}
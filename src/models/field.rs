@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
 
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct StandardField {
     pub id: i32,
     pub field_cn_name: String,
@@ -11,14 +11,71 @@ pub struct StandardField {
     pub data_type: Option<String>,
     pub associated_terms: Option<String>,
     pub is_standard: bool,
-    pub created_at: Option<DateTime<Utc>>,
+    pub is_deprecated: bool,
+    pub replaced_by: Option<i32>, // 废弃后推荐使用的替代字段
+    pub domain: Option<String>,   // 业务域
+    pub business_definition: Option<String>, // 业务定义
+    pub calculation_rule: Option<String>,    // 计算口径
+    pub example_values: Vec<String>,         // 示例值
+    pub data_owner: Option<String>,          // 数据所有者
+    pub data_steward: Option<String>,        // 数据管家
+    pub source_system: Option<String>,       // 来源系统
+    pub approved_by: Option<i32>,            // 审核为标准的管理员
+    pub approved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct CreateFieldRequest {
     pub field_cn_name: String,
     pub field_en_name: String,
     pub composition_ids: Vec<i32>,
     pub data_type: Option<String>,
     pub associated_terms: Option<String>,
-}
\ No newline at end of file
+    pub domain: Option<String>,
+    #[serde(flatten)]
+    pub governance: FieldGovernance,
+}
+
+/// 字段治理属性（创建/更新字段及字段申请共用）
+///
+/// 更新时未提交（或为 null）的属性保持原值，提交空字符串 / 空数组表示清空。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldGovernance {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub business_definition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calculation_rule: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example_values: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_steward: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_system: Option<String>,
+}
+
+impl FieldGovernance {
+    /// 没有提交任何治理属性
+    pub fn is_empty(&self) -> bool {
+        self.business_definition.is_none()
+            && self.calculation_rule.is_none()
+            && self.example_values.is_none()
+            && self.data_owner.is_none()
+            && self.data_steward.is_none()
+            && self.source_system.is_none()
+    }
+}
+
+/// 字段下的标准代码值 (如 性别代码: 1=男, 2=女)
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CodeItem {
+    pub id: i32,
+    pub field_id: i32,
+    pub code_value: String,
+    pub code_name: String,
+    pub remark: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
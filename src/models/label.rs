@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+/// 可配置多语言名称的实体类型
+pub const ENTITY_ROOT: &str = "root";
+pub const ENTITY_FIELD: &str = "field";
+pub const ENTITY_CODE_ITEM: &str = "code_item";
+pub const ENTITY_TYPES: [&str; 3] = [ENTITY_ROOT, ENTITY_FIELD, ENTITY_CODE_ITEM];
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct LocalizedLabel {
+    pub id: i32,
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub locale: String,
+    pub label: String,
+    pub definition: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// 新增/修改某个语言的名称
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpsertLabel {
+    pub label: String,
+    pub definition: Option<String>,
+}
+
+/// 随实体一起提交的多语言名称（如词根批量导入）
+#[derive(Debug, Clone, Deserialize)]
+pub struct LabelInput {
+    pub locale: String,
+    pub label: String,
+    pub definition: Option<String>,
+}
+
+/// 按 Accept-Language 选中的名称，随实体一起返回
+#[derive(Debug, Clone, Serialize)]
+pub struct LabelView {
+    pub locale: String,
+    pub label: String,
+    pub definition: Option<String>,
+}
+
+/// 导入/导出记录：以业务键定位实体，便于在不同环境间迁移
+/// - root: key = en_abbr
+/// - field: key = field_en_name
+/// - code_item: key = 所属字段的 field_en_name，code_value 为代码值
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LabelRecord {
+    pub entity_type: String,
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_value: Option<String>,
+    pub locale: String,
+    pub label: String,
+    pub definition: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LabelQuery {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<i32>,
+    pub locale: Option<String>,
+    pub q: Option<String>, // 名称/定义模糊匹配
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+/// 报告触发方式
+pub const LINT_MANUAL: &str = "manual";
+pub const LINT_SCHEDULED: &str = "scheduled";
+
+/// 报告概要（列表使用）
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct LintReportSummary {
+    pub id: i32,
+    pub trigger: String,
+    pub error_count: i32,
+    pub warning_count: i32,
+    pub info_count: i32,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct LintReport {
+    pub id: i32,
+    pub trigger: String,
+    pub error_count: i32,
+    pub warning_count: i32,
+    pub info_count: i32,
+    pub findings: serde_json::Value, // [Finding]
+    pub created_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,23 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+/// 发件箱状态
+pub const MAIL_PENDING: &str = "PENDING";
+pub const MAIL_SENT: &str = "SENT";
+pub const MAIL_FAILED: &str = "FAILED";
+
+/// 待发送/已发送的通知邮件
+#[derive(Debug, Serialize, FromRow)]
+pub struct OutboxMail {
+    pub id: i64,
+    pub recipient: String,
+    pub subject: String,
+    pub body: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
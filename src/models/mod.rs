@@ -1,3 +1,13 @@
 pub mod word_root;
 pub mod field;
 pub mod user;
+pub mod task;
+pub mod webhook;
+pub mod mail;
+pub mod pagination;
+pub mod stop_word;
+pub mod segment_dict;
+pub mod abbreviation;
+pub mod lint;
+pub mod synonym;
+pub mod label;
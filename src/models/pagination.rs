@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+/// 单页最大条数
+pub const MAX_PAGE_SIZE: i64 = 200;
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+
+/// 列表查询参数（词根与标准字段共用，不适用的筛选项会被忽略）
+#[derive(Debug, Default, Deserialize)]
+pub struct PaginationQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub q: Option<String>,
+    pub min_score: Option<f32>, // trigram 相似度下限，缺省取 SEARCH_TRGM_THRESHOLD
+
+    // 筛选
+    pub domain: Option<String>,
+    pub data_type: Option<String>,              // 仅字段
+    pub status: Option<String>,                 // 仅字段: standard / draft / deprecated
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub updated_from: Option<DateTime<Utc>>,
+    pub updated_to: Option<DateTime<Utc>>,
+    pub has_synonyms: Option<bool>,
+    pub referenced_by_field: Option<i32>,       // 仅词根: 被指定字段引用
+    pub referenced: Option<bool>,               // 仅词根: 是否被任一字段引用
+    pub root_id: Option<i32>,                   // 仅字段: 组成中包含指定词根
+
+    // 排序与游标
+    pub sort: Option<String>,                   // 如 `-created_at`，`-` 表示降序
+    pub cursor: Option<String>,                 // 上一页返回的 next_cursor，传入后忽略 page
+}
+
+impl PaginationQuery {
+    pub fn page_size(&self) -> i64 {
+        self.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page.unwrap_or(1).max(1) - 1) * self.page_size()
+    }
+}
+
+// 分页响应结构
+#[derive(Debug, Serialize)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub next_cursor: Option<String>, // 没有下一页时为 null
+}
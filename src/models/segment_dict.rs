@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+/// 词条规则
+pub const RULE_NORMAL: &str = "normal"; // 按词频参与切分
+pub const RULE_NEVER_SPLIT: &str = "never_split"; // 始终作为一个词
+pub const RULE_FORCE_SPLIT: &str = "force_split"; // 始终拆开
+pub const RULES: [&str; 3] = [RULE_NORMAL, RULE_NEVER_SPLIT, RULE_FORCE_SPLIT];
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct DictEntry {
+    pub id: i32,
+    pub word: String,
+    pub freq: Option<i32>,   // 为空时由分词器估算
+    pub tag: Option<String>, // 词性
+    pub rule: String,
+    pub remark: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateDictEntry {
+    pub word: String,
+    pub freq: Option<i32>,
+    pub tag: Option<String>,
+    pub rule: Option<String>, // 默认 normal
+    pub remark: Option<String>,
+}
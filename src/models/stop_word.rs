@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct StopWord {
+    pub id: i32,
+    pub word: String,
+    pub remark: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateStopWord {
+    pub word: String,
+    pub remark: Option<String>,
+}
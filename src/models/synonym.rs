@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// 写入词根时同义词冲突的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SynonymPolicy {
+    #[default]
+    Reject, // 存在冲突时拒绝写入 (409)
+    Warn,   // 写入词根，剔除冲突的同义词并返回警告
+}
+
+/// 冲突来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictSource {
+    Synonym, // 已是其他词根的同义词
+    CnName,  // 与其他词根的中文名相同
+}
+
+/// 一条同义词冲突：term 已被 root_id 占用
+#[derive(Debug, Clone, Serialize)]
+pub struct SynonymConflict {
+    pub term: String,
+    pub namespace: String,
+    pub root_id: i32,
+    pub cn_name: String,
+    pub en_abbr: String,
+    pub source: ConflictSource,
+}
+
+/// 待处理的同义词：出现在 claimant_root_ids 的 associated_terms 中，但归属于其他词根（或无归属）
+#[derive(Debug, Serialize)]
+pub struct SynonymClaim {
+    pub term: String,
+    pub namespace: String,
+    pub owner_root_id: Option<i32>,
+    pub owner_cn_name: Option<String>,
+    pub claimant_root_ids: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveSynonym {
+    pub term: String,
+    pub root_id: i32, // 保留该同义词的词根，其余词根上的同名同义词被移除
+}
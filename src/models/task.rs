@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+use crate::models::field::FieldGovernance;
+
+/// 任务状态：待处理 / 已审批并落库 / 已关闭(仅标记处理)
+pub const TASK_PENDING: &str = "PENDING";
+pub const TASK_APPLIED: &str = "APPLIED";
+pub const TASK_CLOSED: &str = "CLOSED";
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct NotificationTask {
+    pub id: i32,
+    pub task_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub is_read: bool,
+    pub requester_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// 申请新增标准字段 (FIELD_REQUEST)
+/// 用户端通常只填写中文名，其余信息由管理员在审批时补全
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldRequestPayload {
+    pub field_cn_name: String,
+    pub field_en_name: Option<String>,
+    pub composition_ids: Option<Vec<i32>>,
+    pub data_type: Option<String>,
+    pub associated_terms: Option<String>,
+    pub reason: Option<String>,
+    /// 治理属性，可由申请人填写或管理员在审批时补全
+    #[serde(flatten)]
+    pub governance: FieldGovernance,
+}
+
+/// 申请新增词根 (ROOT_REQUEST)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootRequestPayload {
+    pub cn_name: String,
+    pub en_abbr: String,
+    pub en_full_name: Option<String>,
+    pub associated_terms: Option<String>,
+    /// 业务域，同时决定同义词所在的命名空间
+    pub domain: Option<String>,
+    /// 申请理由，仅供审批参考，不写入词根
+    pub reason: Option<String>,
+    /// 词根备注，可由管理员在审批时补充
+    pub remark: Option<String>,
+}
+
+/// 申请修改已有字段 (FIELD_CHANGE)，仅提交需要变更的属性
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChangePayload {
+    pub field_id: i32,
+    pub field_cn_name: Option<String>,
+    pub field_en_name: Option<String>,
+    pub composition_ids: Option<Vec<i32>>,
+    pub data_type: Option<String>,
+    pub associated_terms: Option<String>,
+    pub reason: Option<String>,
+    /// 需要变更的治理属性，空字符串 / 空数组表示清空
+    #[serde(flatten)]
+    pub governance: FieldGovernance,
+}
+
+/// 申请废弃字段 (FIELD_DEPRECATION)，可指定替代字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDeprecationPayload {
+    pub field_id: i32,
+    pub replaced_by: Option<i32>,
+    pub reason: String,
+}
+
+/// 申请为字段新增代码值 (CODE_VALUE_REQUEST)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeValuePayload {
+    pub field_id: i32,
+    pub code_value: String,
+    pub code_name: String,
+    pub remark: Option<String>,
+}
+
+/// 带类型的申请：`task_type` 决定 `payload` 的结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "task_type", content = "payload", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TaskRequest {
+    FieldRequest(FieldRequestPayload),
+    RootRequest(RootRequestPayload),
+    FieldChange(FieldChangePayload),
+    FieldDeprecation(FieldDeprecationPayload),
+    CodeValueRequest(CodeValuePayload),
+}
+
+fn require(value: &str, name: &str) -> Result<(), String> {
+    if value.trim().is_empty() {
+        return Err(format!("{} 不能为空", name));
+    }
+    Ok(())
+}
+
+/// 英文名/缩写只允许小写字母、数字与下划线
+fn require_snake_case(value: &str, name: &str) -> Result<(), String> {
+    require(value, name)?;
+    if !value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+        return Err(format!("{} 只能包含小写字母、数字和下划线", name));
+    }
+    Ok(())
+}
+
+fn require_id(id: i32, name: &str) -> Result<(), String> {
+    if id <= 0 {
+        return Err(format!("{} 无效", name));
+    }
+    Ok(())
+}
+
+impl TaskRequest {
+    pub fn task_type(&self) -> &'static str {
+        match self {
+            TaskRequest::FieldRequest(_) => "FIELD_REQUEST",
+            TaskRequest::RootRequest(_) => "ROOT_REQUEST",
+            TaskRequest::FieldChange(_) => "FIELD_CHANGE",
+            TaskRequest::FieldDeprecation(_) => "FIELD_DEPRECATION",
+            TaskRequest::CodeValueRequest(_) => "CODE_VALUE_REQUEST",
+        }
+    }
+
+    /// 提交时的结构校验
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            TaskRequest::FieldRequest(p) => {
+                require(&p.field_cn_name, "field_cn_name")?;
+                if let Some(en) = &p.field_en_name {
+                    require_snake_case(en, "field_en_name")?;
+                }
+                Ok(())
+            }
+            TaskRequest::RootRequest(p) => {
+                require(&p.cn_name, "cn_name")?;
+                require_snake_case(&p.en_abbr, "en_abbr")
+            }
+            TaskRequest::FieldChange(p) => {
+                require_id(p.field_id, "field_id")?;
+                if let Some(cn) = &p.field_cn_name {
+                    require(cn, "field_cn_name")?;
+                }
+                if let Some(en) = &p.field_en_name {
+                    require_snake_case(en, "field_en_name")?;
+                }
+                let has_change = p.field_cn_name.is_some()
+                    || p.field_en_name.is_some()
+                    || p.composition_ids.is_some()
+                    || p.data_type.is_some()
+                    || p.associated_terms.is_some()
+                    || !p.governance.is_empty();
+                if !has_change {
+                    return Err("至少需要提交一项变更内容".to_string());
+                }
+                Ok(())
+            }
+            TaskRequest::FieldDeprecation(p) => {
+                require_id(p.field_id, "field_id")?;
+                if let Some(target) = p.replaced_by {
+                    require_id(target, "replaced_by")?;
+                    if target == p.field_id {
+                        return Err("替代字段不能是其本身".to_string());
+                    }
+                }
+                require(&p.reason, "reason")
+            }
+            TaskRequest::CodeValueRequest(p) => {
+                require_id(p.field_id, "field_id")?;
+                require(&p.code_value, "code_value")?;
+                require(&p.code_name, "code_name")
+            }
+        }
+    }
+
+    /// 从数据库记录还原带类型的申请
+    pub fn from_task(task_type: &str, payload: serde_json::Value) -> Result<Self, String> {
+        serde_json::from_value(serde_json::json!({ "task_type": task_type, "payload": payload }))
+            .map_err(|e| format!("任务内容解析失败: {}", e))
+    }
+
+    pub fn payload_json(&self) -> serde_json::Value {
+        match self {
+            TaskRequest::FieldRequest(p) => serde_json::to_value(p),
+            TaskRequest::RootRequest(p) => serde_json::to_value(p),
+            TaskRequest::FieldChange(p) => serde_json::to_value(p),
+            TaskRequest::FieldDeprecation(p) => serde_json::to_value(p),
+            TaskRequest::CodeValueRequest(p) => serde_json::to_value(p),
+        }
+        .unwrap_or_default()
+    }
+}
@@ -8,9 +8,20 @@ pub struct User {
     pub username: String,
     pub password_hash: String,
     pub role: String,
+    pub email: Option<String>,
+    pub locale: String,         // 通知语言: zh / en
+    pub email_opt_out: bool,    // 是否退订邮件通知
     pub created_at: Option<DateTime<Utc>>,
 }
 
+/// 邮件通知偏好
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationPrefs {
+    pub email: Option<String>,
+    pub locale: Option<String>,
+    pub email_opt_out: Option<bool>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: i32,      // user_id
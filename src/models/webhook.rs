@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+/// 投递状态
+pub const DELIVERY_PENDING: &str = "PENDING";
+pub const DELIVERY_SUCCEEDED: &str = "SUCCEEDED";
+pub const DELIVERY_FAILED: &str = "FAILED";
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct WebhookSubscription {
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+    #[serde(skip_serializing)] // 签名密钥只在创建时返回一次
+    pub secret: String,
+    pub event_types: Vec<String>, // 空数组表示订阅全部事件
+    pub is_active: bool,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// 创建订阅的响应：附带签名密钥，之后的查询不再返回
+#[derive(Serialize)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+#[derive(Deserialize)]
+pub struct CreateWebhook {
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>, // 不填则自动生成
+    pub event_types: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub subscription_id: i32,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
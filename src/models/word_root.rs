@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
 
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+use crate::models::label::LabelInput;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct WordRoot {
     pub id: i32,
     pub cn_name: String,
@@ -10,7 +12,9 @@ pub struct WordRoot {
     pub en_full_name: Option<String>,
     pub associated_terms: Option<String>, // 对应 SQL 的 TEXT
     pub remark: Option<String>,
-    pub created_at: Option<DateTime<Utc>>,
+    pub domain: Option<String>,           // 业务域 (如：客户、财务)
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Deserialize)]
@@ -20,4 +24,17 @@ pub struct CreateWordRoot {
     pub en_full_name: Option<String>,
     pub associated_terms: Option<String>, // 用户输入如："钱,费用,价格"
     pub remark: Option<String>,
-}
\ No newline at end of file
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub labels: Option<Vec<LabelInput>>,  // 多语言名称，按 locale 新增或覆盖，未提交的语言保持不变
+}
+
+/// 规范化同义词字符串（将各种分隔符统一为空格，压缩多余空格）
+pub fn normalize_terms(input: Option<String>) -> Option<String> {
+    input.map(|s| {
+        s.replace([',', '，'], " ") // 英文逗号 / 中文逗号
+         .split_whitespace()       // 自动处理多空格
+         .collect::<Vec<_>>()
+         .join(" ")                // 单空格连接
+    })
+}
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+use crate::models::word_root::WordRoot;
+use crate::services::mapping_service;
+
+/// 多词英文全称中生成缩写时跳过的虚词
+const FILLER_WORDS: &[&str] = &["a", "an", "and", "by", "for", "in", "of", "on", "or", "the", "to"];
+/// 不超过该长度的单词在组合时原样保留
+const SHORT_WORD_LEN: usize = 4;
+
+/// 缩写生成规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbbrRule {
+    Known,        // 常用缩写表
+    ExistingRoot, // 已有词根的英文全称 → 缩写，保持与现有约定一致
+    DropVowels,   // 保留首字母，去掉其余元音并合并重复辅音（number → nmbr）
+    FirstN,       // 取前 N 个字母（customer → cus / cust）
+    Initials,     // 多词取各词首字母（value added tax → vat）
+    FullName,     // 英文全称本身（snake_case）
+}
+
+pub const ALL_RULES: [AbbrRule; 6] = [
+    AbbrRule::Known,
+    AbbrRule::ExistingRoot,
+    AbbrRule::DropVowels,
+    AbbrRule::FirstN,
+    AbbrRule::Initials,
+    AbbrRule::FullName,
+];
+
+/// 生成参数
+#[derive(Debug, Clone)]
+pub struct AbbrOptions {
+    pub rules: Vec<AbbrRule>, // 启用的规则，按顺序生成
+    pub first_n: Vec<usize>,  // FirstN 规则取的字母数
+    pub max_len: usize,       // 缩写最大长度（含 `_`）
+}
+
+impl Default for AbbrOptions {
+    fn default() -> Self {
+        Self { rules: ALL_RULES.to_vec(), first_n: vec![3, 4], max_len: 12 }
+    }
+}
+
+/// 与已有缩写的冲突
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    Exact,  // 缩写已被占用，违反唯一约束
+    Prefix, // 按 `_` 分词后与已有缩写互为前缀（如 cust 与 cust_no），物理名解析可能产生歧义
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbbrConflict {
+    pub kind: ConflictKind,
+    pub root_id: i32,
+    pub cn_name: String,
+    pub en_abbr: String,
+    pub en_full_name: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbbrCandidate {
+    pub abbr: String,
+    pub rules: Vec<AbbrRule>, // 能生成该缩写的全部规则
+    pub explanation: String,  // 各单词的取法
+    pub available: bool,      // 没有被占用
+    pub conflicts: Vec<AbbrConflict>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbbrSuggestion {
+    pub cn_name: String,
+    pub en_full_name: String,
+    pub words: Vec<String>,             // 参与生成的英文单词
+    pub existing_roots: Vec<WordRoot>,  // 中文名相同的已有词根，存在时建议复用
+    pub candidates: Vec<AbbrCandidate>, // 无冲突的在前，其次仅有前缀冲突的，已占用的在最后
+    pub recommended: Option<String>,    // 首个无冲突的候选
+}
+
+/// 保留首字母，去掉其余元音并合并连续重复的字母
+fn drop_vowels(word: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in word.chars().enumerate() {
+        if i > 0 && "aeiou".contains(c) {
+            continue;
+        }
+        if !out.ends_with(c) {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// 逐词取缩写并以 `_` 连接，返回 (缩写, 说明, 是否有单词由 `pick` 取得)；`pick` 返回 None 的单词短词保留、长词去元音
+fn combine(words: &[String], pick: impl Fn(&str) -> Option<(String, String)>) -> (String, String, bool) {
+    let mut parts = Vec::with_capacity(words.len());
+    let mut notes = Vec::with_capacity(words.len());
+    let mut any = false;
+    for w in words {
+        let (abbr, note) = match pick(w) {
+            Some(p) => {
+                any = true;
+                p
+            }
+            None if w.len() <= SHORT_WORD_LEN => (w.clone(), "原词".to_string()),
+            None => (drop_vowels(w), "去元音".to_string()),
+        };
+        notes.push(format!("{} → {}（{}）", w, abbr, note));
+        parts.push(abbr);
+    }
+    (parts.join("_"), notes.join("，"), any)
+}
+
+fn is_conventional(abbr: &str) -> bool {
+    abbr.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+        && abbr.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !abbr.ends_with('_')
+        && !abbr.contains("__")
+}
+
+/// 按规则生成 (缩写, 规则, 说明)
+fn generate(
+    words: &[String],
+    known: &HashMap<String, String>,
+    existing: &HashMap<String, String>,
+    options: &AbbrOptions,
+) -> Vec<(String, AbbrRule, String)> {
+    let mut out = Vec::new();
+    for rule in &options.rules {
+        match rule {
+            AbbrRule::Known | AbbrRule::ExistingRoot => {
+                let (table, label) = if *rule == AbbrRule::Known { (known, "常用缩写") } else { (existing, "已有词根") };
+                if let (abbr, note, true) = combine(words, |w| table.get(w).map(|a| (a.clone(), label.to_string()))) {
+                    out.push((abbr, *rule, note));
+                }
+            }
+            AbbrRule::DropVowels => {
+                let (abbr, note, _) = combine(words, |w| (w.len() > 3).then(|| (drop_vowels(w), "去元音".to_string())));
+                out.push((abbr, *rule, note));
+            }
+            AbbrRule::FirstN => {
+                for &n in &options.first_n {
+                    let (abbr, note, _) = combine(words, |w| {
+                        Some(if w.len() > n { (w[..n].to_string(), format!("前 {} 个字母", n)) } else { (w.to_string(), "原词".to_string()) })
+                    });
+                    out.push((abbr, *rule, note));
+                }
+            }
+            AbbrRule::Initials => {
+                if words.len() >= 2 {
+                    let abbr: String = words.iter().filter_map(|w| w.chars().next()).collect();
+                    out.push((abbr, *rule, format!("{} 的首字母", words.join(" "))));
+                }
+            }
+            AbbrRule::FullName => out.push((words.join("_"), *rule, "英文全称".to_string())),
+        }
+    }
+    out
+}
+
+/// 为新词根生成缩写建议，并检查与已有缩写的冲突
+pub async fn suggest(
+    pool: &PgPool,
+    cn_name: &str,
+    en_full_name: &str,
+    options: &AbbrOptions,
+) -> Result<AbbrSuggestion, sqlx::Error> {
+    let all_words = mapping_service::split_identifier(en_full_name);
+    let mut words: Vec<String> = all_words.iter().filter(|w| !FILLER_WORDS.contains(&w.as_str())).cloned().collect();
+    if words.is_empty() {
+        words = all_words;
+    }
+
+    // 1. 常用缩写表与已有词根（单词英文全称）的约定
+    let known: HashMap<String, String> = sqlx::query!(
+        "SELECT word, abbr FROM abbreviation_dictionary WHERE word = ANY($1)",
+        &words
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| (r.word, r.abbr.to_lowercase()))
+    .collect();
+    let existing: HashMap<String, String> = sqlx::query!(
+        r#"SELECT DISTINCT ON (lower(en_full_name)) lower(en_full_name) as "word!", en_abbr
+           FROM standard_word_roots WHERE lower(en_full_name) = ANY($1)
+           ORDER BY lower(en_full_name), id"#,
+        &words
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| (r.word, r.en_abbr.to_lowercase()))
+    .collect();
+
+    // 2. 生成、过滤并合并相同的缩写
+    let mut candidates: Vec<AbbrCandidate> = Vec::new();
+    for (abbr, rule, explanation) in generate(&words, &known, &existing, options) {
+        if abbr.len() > options.max_len || !is_conventional(&abbr) {
+            continue;
+        }
+        match candidates.iter_mut().find(|c| c.abbr == abbr) {
+            Some(c) if !c.rules.contains(&rule) => c.rules.push(rule),
+            Some(_) => {}
+            None => candidates.push(AbbrCandidate { abbr, rules: vec![rule], explanation, available: true, conflicts: vec![] }),
+        }
+    }
+
+    // 3. 一次查询找出全部冲突（前缀按 `_` 分隔的单词比较）
+    let abbrs: Vec<String> = candidates.iter().map(|c| c.abbr.clone()).collect();
+    let rows = sqlx::query!(
+        r#"SELECT c.abbr as "abbr!", r.id, r.cn_name, r.en_abbr, r.en_full_name, (lower(r.en_abbr) = c.abbr) as "exact!"
+           FROM unnest($1::TEXT[]) AS c(abbr)
+           JOIN standard_word_roots r
+             ON lower(r.en_abbr) = c.abbr
+             OR starts_with(lower(r.en_abbr), c.abbr || '_') OR starts_with(c.abbr, lower(r.en_abbr) || '_')
+           ORDER BY r.id"#,
+        &abbrs
+    )
+    .fetch_all(pool)
+    .await?;
+    for r in rows {
+        let Some(c) = candidates.iter_mut().find(|c| c.abbr == r.abbr) else { continue };
+        let (kind, message) = if r.exact {
+            c.available = false;
+            (ConflictKind::Exact, format!("已被词根「{}」(ID={}) 使用", r.cn_name, r.id))
+        } else {
+            (
+                ConflictKind::Prefix,
+                format!("与词根「{}」的缩写 {} 互为前缀，物理名解析可能产生歧义", r.cn_name, r.en_abbr),
+            )
+        };
+        c.conflicts.push(AbbrConflict {
+            kind,
+            root_id: r.id,
+            cn_name: r.cn_name,
+            en_abbr: r.en_abbr,
+            en_full_name: r.en_full_name,
+            message,
+        });
+    }
+    candidates.sort_by_key(|c| (!c.available, !c.conflicts.is_empty()));
+    let recommended = candidates.iter().find(|c| c.conflicts.is_empty()).map(|c| c.abbr.clone());
+
+    // 4. 中文名相同的已有词根
+    let existing_roots = sqlx::query_as!(
+        WordRoot,
+        "SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at
+         FROM standard_word_roots WHERE cn_name = $1 ORDER BY id",
+        cn_name
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(AbbrSuggestion {
+        cn_name: cn_name.to_string(),
+        en_full_name: en_full_name.to_string(),
+        words,
+        existing_roots,
+        candidates,
+        recommended,
+    })
+}
@@ -0,0 +1,342 @@
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::AppState;
+use crate::services::event_service::{ChangeAction, DomainEvent};
+use crate::services::pinyin_service;
+
+/// 单次补全最多扫描的词条数，避免单字母前缀遍历整个索引
+const SCAN_LIMIT: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Root,
+    Field,
+}
+
+/// 命中的词条类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Name,    // 中文名
+    English, // 英文名 / 缩写 / 英文全称
+    Synonym, // 同义词
+    Pinyin,  // 全拼或首字母
+}
+
+impl MatchKind {
+    fn weight(self) -> f32 {
+        match self {
+            MatchKind::Name => 1.0,
+            MatchKind::English => 0.9,
+            MatchKind::Synonym => 0.8,
+            MatchKind::Pinyin => 0.7,
+        }
+    }
+}
+
+/// 索引中的一条倒排记录：规范化后的键 → (实体, 词条类别, 原文)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Posting {
+    entity: EntityType,
+    id: i32,
+    kind: MatchKind,
+    text: String,
+}
+
+/// 一个实体的展示信息及其写入索引的全部键，删除/更新时据此清理
+struct Doc {
+    label: String,
+    en_name: String,
+    deprecated: bool,
+    keys: Vec<(String, Posting)>,
+}
+
+/// 补全结果
+#[derive(Debug, Serialize)]
+pub struct Completion {
+    pub entity_type: EntityType,
+    pub id: i32,
+    pub label: String,   // 中文名
+    pub en_name: String, // 字段英文名 / 词根缩写
+    pub text: String,    // 命中的词条原文
+    pub matched: MatchKind,
+    pub score: f32,
+}
+
+/// 按键有序的前缀索引：前缀查询即 BTreeMap 上的区间扫描，支持按实体增量增删
+#[derive(Default)]
+struct PrefixIndex {
+    keys: BTreeMap<String, BTreeSet<Posting>>,
+    docs: HashMap<(EntityType, i32), Doc>,
+}
+
+impl PrefixIndex {
+    fn remove(&mut self, entity: EntityType, id: i32) {
+        let Some(doc) = self.docs.remove(&(entity, id)) else { return };
+        for (key, posting) in doc.keys {
+            if let Some(set) = self.keys.get_mut(&key) {
+                set.remove(&posting);
+                if set.is_empty() {
+                    self.keys.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn remove_all(&mut self, entity: EntityType) {
+        let ids: Vec<i32> = self.docs.keys().filter(|(e, _)| *e == entity).map(|(_, id)| *id).collect();
+        for id in ids {
+            self.remove(entity, id);
+        }
+    }
+
+    fn insert(&mut self, entity: EntityType, id: i32, mut doc: Doc) {
+        self.remove(entity, id);
+        for (key, posting) in &doc.keys {
+            self.keys.entry(key.clone()).or_default().insert(posting.clone());
+        }
+        doc.keys.shrink_to_fit();
+        self.docs.insert((entity, id), doc);
+    }
+
+    /// 收集以 `prefix` 开头的键，每个实体保留得分最高的一条
+    fn collect(&self, prefix: &str, entity: Option<EntityType>, best: &mut HashMap<(EntityType, i32), Completion>) {
+        let range = self.keys.range::<str, _>((std::ops::Bound::Included(prefix), std::ops::Bound::Unbounded));
+        let mut scanned = 0;
+        for (key, postings) in range.take_while(|(k, _)| k.starts_with(prefix)) {
+            for p in postings {
+                scanned += 1;
+                if entity.is_some_and(|e| e != p.entity) {
+                    continue;
+                }
+                let Some(doc) = self.docs.get(&(p.entity, p.id)) else { continue };
+                // 词条越接近输入得分越高；完全相同额外加分，已废弃字段降权
+                let coverage = prefix.chars().count() as f32 / key.chars().count() as f32;
+                let mut score = p.kind.weight() * (0.5 + 0.4 * coverage);
+                if key.as_str() == prefix {
+                    score += 0.1;
+                }
+                if doc.deprecated {
+                    score *= 0.5;
+                }
+                let slot = (p.entity, p.id);
+                if best.get(&slot).is_none_or(|c| c.score < score) {
+                    best.insert(slot, Completion {
+                        entity_type: p.entity,
+                        id: p.id,
+                        label: doc.label.clone(),
+                        en_name: doc.en_name.clone(),
+                        text: p.text.clone(),
+                        matched: p.kind,
+                        score,
+                    });
+                }
+            }
+            if scanned >= SCAN_LIMIT {
+                break;
+            }
+        }
+    }
+}
+
+static INDEX: Lazy<RwLock<PrefixIndex>> = Lazy::new(|| RwLock::new(PrefixIndex::default()));
+
+fn normalize_key(text: &str) -> String {
+    text.trim().to_lowercase()
+}
+
+/// 组装一个实体的全部索引键
+struct DocBuilder {
+    entity: EntityType,
+    id: i32,
+    keys: Vec<(String, Posting)>,
+}
+
+impl DocBuilder {
+    fn new(entity: EntityType, id: i32) -> Self {
+        Self { entity, id, keys: Vec::new() }
+    }
+
+    fn add(&mut self, kind: MatchKind, text: &str) {
+        let key = normalize_key(text);
+        if key.is_empty() || self.keys.iter().any(|(k, p)| *k == key && p.kind == kind) {
+            return;
+        }
+        let posting = Posting { entity: self.entity, id: self.id, kind, text: text.trim().to_string() };
+        self.keys.push((key, posting));
+    }
+
+    fn add_all(&mut self, kind: MatchKind, text: Option<&str>) {
+        for word in text.unwrap_or("").split([' ', ',', '，']) {
+            self.add(kind, word);
+        }
+    }
+
+    fn build(self, label: String, en_name: String, deprecated: bool) -> Doc {
+        Doc { label, en_name, deprecated, keys: self.keys }
+    }
+}
+
+/// 从数据库加载词根并组装索引文档；`ids` 为 None 时加载全部
+async fn load_roots(pool: &PgPool, ids: Option<&[i32]>) -> Result<Vec<(i32, Doc)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT id, cn_name, en_abbr, en_full_name, associated_terms, cn_pinyin, cn_initials
+           FROM standard_word_roots WHERE $1::INT[] IS NULL OR id = ANY($1)"#,
+        ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let mut b = DocBuilder::new(EntityType::Root, r.id);
+            b.add(MatchKind::Name, &r.cn_name);
+            b.add(MatchKind::English, &r.en_abbr);
+            if let Some(full) = &r.en_full_name {
+                b.add(MatchKind::English, full);
+            }
+            b.add_all(MatchKind::Synonym, r.associated_terms.as_deref());
+            b.add_all(MatchKind::Pinyin, r.cn_pinyin.as_deref());
+            b.add_all(MatchKind::Pinyin, r.cn_initials.as_deref());
+            (r.id, b.build(r.cn_name, r.en_abbr, false))
+        })
+        .collect())
+}
+
+/// 从数据库加载标准字段并组装索引文档
+async fn load_fields(pool: &PgPool, ids: Option<&[i32]>) -> Result<Vec<(i32, Doc)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT id, field_cn_name, field_en_name, associated_terms, cn_pinyin, cn_initials,
+                  COALESCE(is_deprecated, false) as "is_deprecated!"
+           FROM standard_fields WHERE $1::INT[] IS NULL OR id = ANY($1)"#,
+        ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let mut b = DocBuilder::new(EntityType::Field, r.id);
+            b.add(MatchKind::Name, &r.field_cn_name);
+            b.add(MatchKind::English, &r.field_en_name);
+            b.add_all(MatchKind::Synonym, r.associated_terms.as_deref());
+            b.add_all(MatchKind::Pinyin, r.cn_pinyin.as_deref());
+            b.add_all(MatchKind::Pinyin, r.cn_initials.as_deref());
+            (r.id, b.build(r.field_cn_name, r.field_en_name, r.is_deprecated))
+        })
+        .collect())
+}
+
+/// 全量重建索引
+pub async fn rebuild(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let roots = load_roots(pool, None).await?;
+    let fields = load_fields(pool, None).await?;
+
+    let mut index = PrefixIndex::default();
+    for (id, doc) in roots {
+        index.insert(EntityType::Root, id, doc);
+    }
+    for (id, doc) in fields {
+        index.insert(EntityType::Field, id, doc);
+    }
+    tracing::info!("补全索引构建完成: 实体 {} 个, 键 {} 个", index.docs.len(), index.keys.len());
+    *INDEX.write() = index;
+    Ok(())
+}
+
+/// 增量刷新指定实体：数据库中已不存在的 ID 从索引中移除
+async fn refresh(pool: &PgPool, entity: EntityType, ids: &[i32]) -> Result<(), sqlx::Error> {
+    let docs = match entity {
+        EntityType::Root => load_roots(pool, Some(ids)).await?,
+        EntityType::Field => load_fields(pool, Some(ids)).await?,
+    };
+    let mut index = INDEX.write();
+    for id in ids {
+        index.remove(entity, *id);
+    }
+    for (id, doc) in docs {
+        index.insert(entity, id, doc);
+    }
+    Ok(())
+}
+
+async fn apply_event(pool: &PgPool, event: &DomainEvent) -> Result<(), sqlx::Error> {
+    let (entity, action, ids) = match event {
+        DomainEvent::RootsChanged { action, ids } => (EntityType::Root, action, ids),
+        DomainEvent::FieldsChanged { action, ids } => (EntityType::Field, action, ids),
+        _ => return Ok(()),
+    };
+    match action {
+        ChangeAction::Cleared => INDEX.write().remove_all(entity),
+        ChangeAction::Deleted => {
+            let mut index = INDEX.write();
+            for id in ids {
+                index.remove(entity, *id);
+            }
+        }
+        ChangeAction::Created | ChangeAction::Updated => refresh(pool, entity, ids).await?,
+    }
+    Ok(())
+}
+
+/// 订阅领域事件，按词根/字段变更增量维护索引
+///
+/// 开启 `EVENTS_PG_NOTIFY` 时其他副本的变更同样经事件总线到达；事件积压丢失时全量重建。
+pub fn spawn_listener(state: &AppState) {
+    let pool = state.db.clone();
+    let mut rx = state.events.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    if let Err(e) = apply_event(&pool, &event).await {
+                        tracing::warn!("--- 补全索引增量更新失败，转为全量重建: {}", e);
+                        let _ = rebuild(&pool).await;
+                    }
+                }
+                Err(RecvError::Lagged(n)) => {
+                    tracing::warn!("--- 补全索引丢失 {} 个事件，全量重建", n);
+                    if let Err(e) = rebuild(&pool).await {
+                        tracing::error!("!!! 补全索引重建失败: {}", e);
+                    }
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+/// 前缀补全：同时以原输入与拼音规范形式（去空格/隔音符）查询，按得分降序
+pub fn complete(q: &str, entity: Option<EntityType>, limit: usize) -> Vec<Completion> {
+    let prefix = normalize_key(q);
+    if prefix.is_empty() {
+        return vec![];
+    }
+    let mut best = HashMap::new();
+    {
+        let index = INDEX.read();
+        index.collect(&prefix, entity, &mut best);
+        if let Some(py) = pinyin_service::normalize_query(q).filter(|py| *py != prefix) {
+            index.collect(&py, entity, &mut best);
+        }
+    }
+
+    let mut items: Vec<Completion> = best.into_values().collect();
+    items.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.label.chars().count().cmp(&b.label.chars().count()))
+            .then_with(|| a.entity_type.cmp(&b.entity_type))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    items.truncate(limit);
+    items
+}
@@ -0,0 +1,522 @@
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, PgPool, Postgres, QueryBuilder, Row};
+
+use crate::models::field::StandardField;
+use crate::models::label::{ENTITY_FIELD, ENTITY_ROOT};
+use crate::models::pagination::{PaginatedResponse, PaginationQuery};
+use crate::models::word_root::WordRoot;
+use crate::services::pinyin_service;
+use crate::services::search_service::{self, Scored};
+
+type ListResult<T> = Result<T, (StatusCode, String)>;
+
+fn invalid(msg: impl Into<String>) -> (StatusCode, String) {
+    (StatusCode::UNPROCESSABLE_ENTITY, msg.into())
+}
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    tracing::error!("!!! 列表查询失败: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, format!("查询异常: {}", e))
+}
+
+/// 可选的排序键
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    Score,     // 相关度，仅在带检索词时可用
+    CreatedAt,
+    UpdatedAt,
+    Name,      // 中文名
+    EnName,    // 词根缩写 / 字段英文名
+    Id,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Sort {
+    key: SortKey,
+    desc: bool,
+}
+
+impl Sort {
+    /// 解析 `sort` 参数，如 `-created_at`、`name`；缺省时有检索词按相关度降序，否则按创建时间降序
+    fn parse(raw: Option<&str>, has_q: bool) -> ListResult<Self> {
+        let raw = match raw.map(str::trim).filter(|s| !s.is_empty()) {
+            Some(s) => s,
+            None if has_q => return Ok(Sort { key: SortKey::Score, desc: true }),
+            None => return Ok(Sort { key: SortKey::CreatedAt, desc: true }),
+        };
+        let (desc, name) = match raw.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, raw),
+        };
+        let key = match name {
+            "score" if has_q => SortKey::Score,
+            "score" => return Err(invalid("按 score 排序需要提供检索词 q")),
+            "created_at" => SortKey::CreatedAt,
+            "updated_at" => SortKey::UpdatedAt,
+            "name" => SortKey::Name,
+            "en_name" => SortKey::EnName,
+            "id" => SortKey::Id,
+            other => {
+                return Err(invalid(format!(
+                    "未知的排序键: {}，可选: score, created_at, updated_at, name, en_name, id",
+                    other
+                )))
+            }
+        };
+        Ok(Sort { key, desc })
+    }
+
+    fn as_param(&self) -> String {
+        let name = match self.key {
+            SortKey::Score => "score",
+            SortKey::CreatedAt => "created_at",
+            SortKey::UpdatedAt => "updated_at",
+            SortKey::Name => "name",
+            SortKey::EnName => "en_name",
+            SortKey::Id => "id",
+        };
+        if self.desc { format!("-{}", name) } else { name.to_string() }
+    }
+}
+
+/// 游标中记录的排序键取值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum CursorValue {
+    Int(i32),
+    Score(u32),   // 相关度 (REAL) 的位模式，原样还原，避免十进制往返造成的误差
+    Text(String), // 文本或 RFC3339 时间
+}
+
+/// 键集游标：上一页最后一行的 (排序值, id)，以及生成它时的排序方式
+#[derive(Debug, Serialize, Deserialize)]
+struct Cursor {
+    sort: String,
+    value: CursorValue,
+    id: i32,
+}
+
+impl Cursor {
+    fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(raw: &str, sort: Sort) -> ListResult<Self> {
+        let cursor: Cursor = hex::decode(raw)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| invalid("cursor 无效"))?;
+        if cursor.sort != sort.as_param() {
+            return Err(invalid("cursor 与当前排序方式不一致，请从第一页重新获取"));
+        }
+        Ok(cursor)
+    }
+}
+
+/// 列表实体：表别名、各排序键对应的 SQL 表达式及从结果行取排序值
+trait Listable {
+    const ALIAS: &'static str;
+    const ENTITY: &'static str; // localized_labels.entity_type
+    fn sort_expr(key: SortKey) -> &'static str;
+    fn id(&self) -> i32;
+    fn sort_value(&self, key: SortKey) -> CursorValue;
+}
+
+fn ts_value(ts: DateTime<Utc>) -> CursorValue {
+    CursorValue::Text(ts.to_rfc3339())
+}
+
+impl Listable for WordRoot {
+    const ALIAS: &'static str = "r";
+    const ENTITY: &'static str = ENTITY_ROOT;
+
+    fn sort_expr(key: SortKey) -> &'static str {
+        match key {
+            SortKey::Score => "r.score",
+            SortKey::CreatedAt => "r.created_at",
+            SortKey::UpdatedAt => "r.updated_at",
+            SortKey::Name => "r.cn_name",
+            SortKey::EnName => "r.en_abbr",
+            SortKey::Id => "r.id",
+        }
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn sort_value(&self, key: SortKey) -> CursorValue {
+        match key {
+            SortKey::CreatedAt => ts_value(self.created_at),
+            SortKey::UpdatedAt => ts_value(self.updated_at),
+            SortKey::Name => CursorValue::Text(self.cn_name.clone()),
+            SortKey::EnName => CursorValue::Text(self.en_abbr.clone()),
+            SortKey::Score | SortKey::Id => CursorValue::Int(self.id),
+        }
+    }
+}
+
+impl Listable for StandardField {
+    const ALIAS: &'static str = "f";
+    const ENTITY: &'static str = ENTITY_FIELD;
+
+    fn sort_expr(key: SortKey) -> &'static str {
+        match key {
+            SortKey::Score => "f.score",
+            SortKey::CreatedAt => "f.created_at",
+            SortKey::UpdatedAt => "f.updated_at",
+            SortKey::Name => "f.field_cn_name",
+            SortKey::EnName => "f.field_en_name",
+            SortKey::Id => "f.id",
+        }
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn sort_value(&self, key: SortKey) -> CursorValue {
+        match key {
+            SortKey::CreatedAt => ts_value(self.created_at),
+            SortKey::UpdatedAt => ts_value(self.updated_at),
+            SortKey::Name => CursorValue::Text(self.field_cn_name.clone()),
+            SortKey::EnName => CursorValue::Text(self.field_en_name.clone()),
+            SortKey::Score | SortKey::Id => CursorValue::Int(self.id),
+        }
+    }
+}
+
+/// 检索词相关的 CTE
+///
+/// `p`：`p.q` 为检索词，`p.pattern` 为子串匹配模式，`p.py` 为拼音形式的检索词（非拼音输入为 NULL），
+/// `p.py_pattern` 为拼音子串模式（可走 trigram 索引），`p.py_prefix` 匹配拼音索引中任一词条的前缀。
+/// `label_hits`：名称或定义命中检索词的多语言名称，按实体聚合出名称的最高相似度；
+/// 先用 trigram 运算符与 ILIKE 在标签表上筛选（均走索引），再按 `entity_id` 与主表连接，不逐行关联子查询。
+fn push_search_ctes<'a, T: Listable>(b: &mut QueryBuilder<'a, Postgres>, q: &'a str) {
+    let py = pinyin_service::normalize_query(q);
+    let py_pattern = py.as_ref().map(|py| format!("%{}%", py));
+    let py_prefix = py.as_ref().map(|py| format!("% {}%", py));
+    b.push("p AS (SELECT ")
+        .push_bind(q)
+        .push("::TEXT AS q, ")
+        .push_bind(format!("%{}%", q))
+        .push("::TEXT AS pattern, ")
+        .push_bind(py)
+        .push("::TEXT AS py, ")
+        .push_bind(py_pattern)
+        .push("::TEXT AS py_pattern, ")
+        .push_bind(py_prefix)
+        .push("::TEXT AS py_prefix), ");
+    // 条件直接绑定检索词而非引用 p，规划器才能用上 label / definition 的 trigram 索引
+    let pattern = format!("%{}%", q);
+    b.push("label_hits AS (SELECT l.entity_id, max(word_similarity(")
+        .push_bind(q)
+        .push(format!(
+            ", l.label)) AS score FROM localized_labels l WHERE l.entity_type = '{}' AND (",
+            T::ENTITY
+        ))
+        .push_bind(q)
+        .push(" <% l.label OR l.label ILIKE ")
+        .push_bind(pattern.clone())
+        .push(" OR l.definition ILIKE ")
+        .push_bind(pattern)
+        .push(") GROUP BY l.entity_id), ");
+}
+
+/// 检索阈值：未指定时取 `SEARCH_TRGM_THRESHOLD`，指定时限制在 0~1
+fn score_threshold(query: &PaginationQuery) -> f32 {
+    query
+        .min_score
+        .filter(|s| s.is_finite())
+        .map_or_else(search_service::trgm_threshold, |s| s.clamp(0.0, 1.0))
+}
+
+/// 创建/更新时间范围与业务域筛选（两类实体共用）
+fn push_common_filters<'a, T: Listable>(b: &mut QueryBuilder<'a, Postgres>, query: &'a PaginationQuery) {
+    let a = T::ALIAS;
+    if let Some(domain) = query.domain.as_deref().filter(|d| !d.is_empty()) {
+        b.push(format!(" AND {a}.domain = ")).push_bind(domain);
+    }
+    if let Some(ts) = query.created_from {
+        b.push(format!(" AND {a}.created_at >= ")).push_bind(ts);
+    }
+    if let Some(ts) = query.created_to {
+        b.push(format!(" AND {a}.created_at < ")).push_bind(ts);
+    }
+    if let Some(ts) = query.updated_from {
+        b.push(format!(" AND {a}.updated_at >= ")).push_bind(ts);
+    }
+    if let Some(ts) = query.updated_to {
+        b.push(format!(" AND {a}.updated_at < ")).push_bind(ts);
+    }
+    if let Some(has) = query.has_synonyms {
+        b.push(format!(" AND (COALESCE(btrim({a}.associated_terms), '') <> '') = ")).push_bind(has);
+    }
+}
+
+/// 键集条件：(排序值, id) 严格位于游标之后
+fn push_keyset<'a, T: Listable>(
+    b: &mut QueryBuilder<'a, Postgres>,
+    sort: Sort,
+    cursor: &Cursor,
+) -> ListResult<()> {
+    b.push(format!(" AND ({}, {}.id) {} (", T::sort_expr(sort.key), T::ALIAS, if sort.desc { "<" } else { ">" }));
+    match (sort.key, &cursor.value) {
+        (SortKey::CreatedAt | SortKey::UpdatedAt, CursorValue::Text(v)) => {
+            let ts = DateTime::parse_from_rfc3339(v).map_err(|_| invalid("cursor 无效"))?;
+            b.push_bind(ts.with_timezone(&Utc));
+        }
+        (SortKey::Name | SortKey::EnName, CursorValue::Text(v)) => {
+            b.push_bind(v.clone());
+        }
+        (SortKey::Score, CursorValue::Score(bits)) => {
+            b.push_bind(f32::from_bits(*bits));
+        }
+        (SortKey::Id, CursorValue::Int(v)) => {
+            b.push_bind(*v);
+        }
+        _ => return Err(invalid("cursor 无效")),
+    }
+    b.push(", ").push_bind(cursor.id).push(")");
+    Ok(())
+}
+
+fn push_order<T: Listable>(b: &mut QueryBuilder<'_, Postgres>, sort: Sort) {
+    let dir = if sort.desc { "DESC" } else { "ASC" };
+    b.push(format!(" ORDER BY {} {dir}, {}.id {dir}", T::sort_expr(sort.key), T::ALIAS));
+}
+
+/// 分页查询的结果行：总数与（可能为空的）当前页记录
+///
+/// 总数与当前页在同一条语句中从筛选结果得出，页为空时仍返回一行只带总数的记录。
+struct PageRow<T> {
+    total: i64,
+    item: Option<Scored<T>>,
+}
+
+impl<'r, T: FromRow<'r, PgRow>> FromRow<'r, PgRow> for PageRow<T> {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let item = match row.try_get::<Option<i32>, _>("id")? {
+            Some(_) => Some(Scored::from_row(row)?),
+            None => None,
+        };
+        Ok(PageRow { total: row.try_get("total")?, item })
+    }
+}
+
+/// 组装分页语句：筛选结果放在 `filtered` CTE 中，总数与当前页都从它得出
+///
+/// 带检索词时先定义检索参数与标签命中（见 [`push_search_ctes`]），并物化 CTE，相似度只计算一次；
+/// 否则内联，排序与键集条件可直接使用 (排序列, id) 索引。
+async fn fetch_page<'a, T>(
+    conn: &mut PgConnection,
+    columns: &str,
+    push_from_where: impl Fn(&mut QueryBuilder<'a, Postgres>),
+    q: Option<&'a str>,
+    sort: Sort,
+    cursor: Option<&Cursor>,
+    query: &PaginationQuery,
+) -> ListResult<(Vec<Scored<T>>, i64)>
+where
+    T: Listable + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+    let a = T::ALIAS;
+    let has_q = q.is_some();
+    let mut b = QueryBuilder::new("WITH ");
+    if let Some(q) = q {
+        push_search_ctes::<T>(&mut b, q);
+    }
+    b.push("filtered AS ");
+    b.push(if has_q { "MATERIALIZED (SELECT " } else { "NOT MATERIALIZED (SELECT " });
+    b.push(columns);
+    b.push(if has_q { ", s.score" } else { ", NULL::REAL AS score" });
+    push_from_where(&mut b);
+    b.push(format!(
+        ") SELECT t.total, {a}.* FROM (SELECT count(*) AS total FROM filtered) t
+           LEFT JOIN LATERAL (SELECT * FROM filtered {a} WHERE TRUE"
+    ));
+    if let Some(cursor) = cursor {
+        push_keyset::<T>(&mut b, sort, cursor)?;
+    }
+    push_order::<T>(&mut b, sort);
+    b.push(" LIMIT ").push_bind(query.page_size() + 1);
+    if cursor.is_none() {
+        b.push(" OFFSET ").push_bind(query.offset());
+    }
+    b.push(format!(") {a} ON TRUE"));
+    push_order::<T>(&mut b, sort);
+
+    let rows = b.build_query_as::<PageRow<T>>().fetch_all(conn).await.map_err(db_error)?;
+    let total = rows.first().map_or(0, |r| r.total);
+    Ok((rows.into_iter().filter_map(|r| r.item).collect(), total))
+}
+
+/// 多取一行判断是否还有下一页，并据此生成 next_cursor
+fn finish_page<T: Listable>(
+    mut items: Vec<Scored<T>>,
+    total: i64,
+    page_size: i64,
+    sort: Sort,
+) -> PaginatedResponse<Scored<T>> {
+    let has_more = items.len() as i64 > page_size;
+    items.truncate(page_size as usize);
+    let next_cursor = items.last().filter(|_| has_more).map(|last| {
+        let value = match sort.key {
+            SortKey::Score => CursorValue::Score(last.score.unwrap_or_default().to_bits()),
+            key => last.item.sort_value(key),
+        };
+        Cursor { sort: sort.as_param(), value, id: last.item.id() }.encode()
+    });
+    PaginatedResponse { items, total, next_cursor }
+}
+
+/// 词根列表：检索、筛选、排序与分页
+///
+/// 带 `q` 时按中文名/缩写/全称/同义词的 trigram 相似度打分，达到阈值或子串命中的词根才返回。
+/// 命中判断使用 `%` / `<%` 运算符与 ILIKE，均可走 GIN trigram 索引，得分只对命中的词根计算。
+/// 传入 `cursor` 时使用键集分页（忽略 `page`），大数据量下翻页稳定且不随页码变慢。
+pub async fn list_roots<'a>(pool: &PgPool, query: &'a PaginationQuery) -> ListResult<PaginatedResponse<Scored<WordRoot>>> {
+    let q = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+    let sort = Sort::parse(query.sort.as_deref(), q.is_some())?;
+    let cursor = query.cursor.as_deref().map(|c| Cursor::decode(c, sort)).transpose()?;
+    let min_score = score_threshold(query);
+    let page_size = query.page_size();
+
+    let push_from_where = |b: &mut QueryBuilder<'a, Postgres>| {
+        b.push(" FROM standard_word_roots r");
+        if q.is_some() {
+            b.push(
+                " CROSS JOIN p LEFT JOIN label_hits lh ON lh.entity_id = r.id,
+                 LATERAL (SELECT GREATEST(similarity(r.cn_name, p.q), word_similarity(p.q, r.cn_name),
+                        similarity(r.en_abbr, p.q), COALESCE(word_similarity(p.q, r.en_full_name), 0),
+                        COALESCE(word_similarity(p.q, r.associated_terms), 0),
+                        COALESCE(word_similarity(p.py, r.cn_pinyin), 0), COALESCE(word_similarity(p.py, r.cn_initials), 0),
+                        COALESCE(lh.score, 0))::REAL AS score) s",
+            );
+        }
+        b.push(" WHERE TRUE");
+        if q.is_some() {
+            b.push(
+                " AND r.id IN (
+                    SELECT h.id FROM standard_word_roots h
+                    WHERE h.cn_name % p.q OR p.q <% h.cn_name OR h.en_abbr % p.q OR p.q <% h.en_full_name
+                       OR p.q <% h.associated_terms OR p.py <% h.cn_pinyin OR p.py <% h.cn_initials
+                       OR h.cn_name ILIKE p.pattern OR h.en_abbr ILIKE p.pattern OR h.en_full_name ILIKE p.pattern
+                       OR h.associated_terms ILIKE p.pattern
+                       OR (h.cn_pinyin LIKE p.py_pattern AND ' ' || h.cn_pinyin LIKE p.py_prefix)
+                       OR (h.cn_initials LIKE p.py_pattern AND ' ' || h.cn_initials LIKE p.py_prefix)
+                    UNION
+                    SELECT entity_id FROM label_hits)",
+            );
+        }
+        push_common_filters::<WordRoot>(b, query);
+        if let Some(field_id) = query.referenced_by_field {
+            b.push(" AND r.id IN (SELECT unnest(composition_ids) FROM standard_fields WHERE id = ")
+                .push_bind(field_id)
+                .push(")");
+        }
+        if let Some(referenced) = query.referenced {
+            b.push(" AND EXISTS (SELECT 1 FROM standard_fields sf WHERE r.id = ANY(sf.composition_ids)) = ")
+                .push_bind(referenced);
+        }
+    };
+
+    // 阈值通过事务级配置传给 trigram 运算符
+    let mut tx = pool.begin().await.map_err(db_error)?;
+    search_service::set_trgm_threshold(&mut tx, min_score).await.map_err(db_error)?;
+
+    let (items, total) = fetch_page::<WordRoot>(
+        &mut tx,
+        "r.id, r.cn_name, r.en_abbr, r.en_full_name, r.associated_terms, r.remark, r.domain, r.created_at, r.updated_at",
+        push_from_where,
+        q,
+        sort,
+        cursor.as_ref(),
+        query,
+    )
+    .await?;
+    tx.commit().await.map_err(db_error)?;
+    Ok(finish_page(items, total, page_size, sort))
+}
+
+/// 标准字段列表：检索、筛选、排序与分页（规则同 [`list_roots`]）
+pub async fn list_fields<'a>(
+    pool: &PgPool,
+    query: &'a PaginationQuery,
+) -> ListResult<PaginatedResponse<Scored<StandardField>>> {
+    let q = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+    let sort = Sort::parse(query.sort.as_deref(), q.is_some())?;
+    let cursor = query.cursor.as_deref().map(|c| Cursor::decode(c, sort)).transpose()?;
+    let min_score = score_threshold(query);
+    let page_size = query.page_size();
+
+    let status = match query.status.as_deref().filter(|s| !s.is_empty()) {
+        None => None,
+        Some("standard") => Some(" AND COALESCE(f.is_standard, false) AND NOT f.is_deprecated"),
+        Some("draft") => Some(" AND NOT COALESCE(f.is_standard, false) AND NOT f.is_deprecated"),
+        Some("deprecated") => Some(" AND f.is_deprecated"),
+        Some(other) => return Err(invalid(format!("未知的状态: {}，可选: standard, draft, deprecated", other))),
+    };
+
+    let push_from_where = |b: &mut QueryBuilder<'a, Postgres>| {
+        b.push(" FROM standard_fields f");
+        if q.is_some() {
+            b.push(
+                " CROSS JOIN p LEFT JOIN label_hits lh ON lh.entity_id = f.id,
+                 LATERAL (SELECT GREATEST(similarity(f.field_cn_name, p.q), word_similarity(p.q, f.field_cn_name),
+                        similarity(f.field_en_name, p.q), word_similarity(p.q, f.field_en_name),
+                        COALESCE(word_similarity(p.q, f.associated_terms), 0),
+                        COALESCE(word_similarity(p.q, f.business_definition), 0),
+                        COALESCE(word_similarity(p.py, f.cn_pinyin), 0), COALESCE(word_similarity(p.py, f.cn_initials), 0),
+                        COALESCE(lh.score, 0))::REAL AS score) s",
+            );
+        }
+        b.push(" WHERE TRUE");
+        if q.is_some() {
+            b.push(
+                " AND f.id IN (
+                    SELECT h.id FROM standard_fields h
+                    WHERE h.field_cn_name % p.q OR p.q <% h.field_cn_name OR h.field_en_name % p.q OR p.q <% h.field_en_name
+                       OR p.q <% h.associated_terms OR p.q <% h.business_definition
+                       OR p.py <% h.cn_pinyin OR p.py <% h.cn_initials
+                       OR h.field_cn_name ILIKE p.pattern OR h.field_en_name ILIKE p.pattern
+                       OR h.associated_terms ILIKE p.pattern OR h.business_definition ILIKE p.pattern
+                       OR (h.cn_pinyin LIKE p.py_pattern AND ' ' || h.cn_pinyin LIKE p.py_prefix)
+                       OR (h.cn_initials LIKE p.py_pattern AND ' ' || h.cn_initials LIKE p.py_prefix)
+                    UNION
+                    SELECT entity_id FROM label_hits)",
+            );
+        }
+        push_common_filters::<StandardField>(b, query);
+        if let Some(data_type) = query.data_type.as_deref().filter(|d| !d.is_empty()) {
+            b.push(" AND f.data_type ILIKE ").push_bind(data_type);
+        }
+        if let Some(status) = status {
+            b.push(status);
+        }
+        if let Some(root_id) = query.root_id {
+            b.push(" AND ").push_bind(root_id).push(" = ANY(f.composition_ids)");
+        }
+    };
+
+    // 阈值通过事务级配置传给 trigram 运算符
+    let mut tx = pool.begin().await.map_err(db_error)?;
+    search_service::set_trgm_threshold(&mut tx, min_score).await.map_err(db_error)?;
+
+    let (items, total) = fetch_page::<StandardField>(
+        &mut tx,
+        "f.id, f.field_cn_name, f.field_en_name, COALESCE(f.composition_ids, '{}') AS composition_ids,
+         f.data_type, f.associated_terms, COALESCE(f.is_standard, false) AS is_standard, f.is_deprecated,
+         f.replaced_by, f.domain, f.business_definition, f.calculation_rule, f.example_values, f.data_owner, f.data_steward, f.source_system, f.approved_by, f.approved_at, f.created_at, f.updated_at",
+        push_from_where,
+        q,
+        sort,
+        cursor.as_ref(),
+        query,
+    )
+    .await?;
+    tx.commit().await.map_err(db_error)?;
+    Ok(finish_page(items, total, page_size, sort))
+}
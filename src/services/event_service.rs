@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use tokio::sync::broadcast;
+
+/// Postgres LISTEN/NOTIFY 使用的频道名
+const NOTIFY_CHANNEL: &str = "dict_events";
+/// pg_notify 的载荷上限为 8000 字节，预留余量
+const NOTIFY_PAYLOAD_LIMIT: usize = 7900;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Created,
+    Updated,
+    Deleted,
+    Cleared,
+}
+
+/// 字典领域事件：任务提交/处理，词根、标准字段、停用词与自定义分词词典变更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEvent {
+    TaskSubmitted { task_id: i32, task_type: String },
+    TaskResolved { task_ids: Vec<i32>, status: String },
+    RootsChanged { action: ChangeAction, ids: Vec<i32> },
+    FieldsChanged { action: ChangeAction, ids: Vec<i32> },
+    StopWordsChanged { action: ChangeAction, ids: Vec<i32> },
+    SegmentDictChanged { action: ChangeAction, ids: Vec<i32> },
+}
+
+impl DomainEvent {
+    /// 全部事件名（webhook 可订阅的事件类型），新增事件时与 [`DomainEvent::name`] 一并维护
+    pub const NAMES: [&'static str; 6] = [
+        "task_submitted",
+        "task_resolved",
+        "roots_changed",
+        "fields_changed",
+        "stop_words_changed",
+        "segment_dict_changed",
+    ];
+
+    /// 事件名（SSE 的 `event:` 字段）
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::TaskSubmitted { .. } => "task_submitted",
+            DomainEvent::TaskResolved { .. } => "task_resolved",
+            DomainEvent::RootsChanged { .. } => "roots_changed",
+            DomainEvent::FieldsChanged { .. } => "fields_changed",
+            DomainEvent::StopWordsChanged { .. } => "stop_words_changed",
+            DomainEvent::SegmentDictChanged { .. } => "segment_dict_changed",
+        }
+    }
+}
+
+/// NOTIFY 载荷：携带发布方副本的标识，LISTEN 任务据此跳过本副本发出的事件
+#[derive(Serialize, Deserialize)]
+struct Envelope<E> {
+    origin: u64,
+    event: E,
+}
+
+/// 进程内事件总线
+///
+/// 事件总是先在本进程内广播；开启 `EVENTS_PG_NOTIFY=true` 后，事件同时经 Postgres NOTIFY 发出，
+/// 由其他副本的 LISTEN 任务转发到各自的本地广播，从而保证多副本的订阅者收到一致的事件流。
+pub struct EventBus {
+    sender: broadcast::Sender<DomainEvent>,
+    local: broadcast::Sender<DomainEvent>, // 仅本副本发布的事件
+    pool: PgPool,
+    pg_notify: bool,
+    origin: u64, // 本副本标识，启动时随机生成
+}
+
+impl EventBus {
+    pub fn new(pool: PgPool, pg_notify: bool) -> Self {
+        let (sender, _) = broadcast::channel(1024);
+        let (local, _) = broadcast::channel(1024);
+        Self { sender, local, pool, pg_notify, origin: rand::random() }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+
+    /// 只订阅本副本发布的事件
+    ///
+    /// 用于 Webhook / 通知邮件入队等在整个集群中每个事件只应处理一次的订阅者。
+    pub fn subscribe_local(&self) -> broadcast::Receiver<DomainEvent> {
+        self.local.subscribe()
+    }
+
+    /// 发布事件；事件通知失败不影响业务本身，只记录日志
+    pub async fn publish(&self, event: DomainEvent) {
+        if self.pg_notify {
+            let envelope = Envelope { origin: self.origin, event: &event };
+            match serde_json::to_string(&envelope) {
+                Ok(text) if text.len() <= NOTIFY_PAYLOAD_LIMIT => {
+                    let res = sqlx::query!("SELECT pg_notify($1, $2)", NOTIFY_CHANNEL, text)
+                        .execute(&self.pool)
+                        .await;
+                    if let Err(e) = res {
+                        tracing::warn!("--- 事件 NOTIFY 失败，其他副本将收不到该事件: {}", e);
+                    }
+                }
+                Ok(_) => tracing::warn!("--- 事件载荷超过 NOTIFY 上限，仅本地广播: {}", event.name()),
+                Err(e) => tracing::warn!("--- 事件序列化失败: {}", e),
+            }
+        }
+        // 本地广播不依赖 LISTEN 任务；没有订阅者时 send 会返回错误，属于正常情况
+        let _ = self.local.send(event.clone());
+        let _ = self.sender.send(event);
+    }
+
+    /// 启动 LISTEN 任务，将其他副本 NOTIFY 的事件转发到本地广播
+    ///
+    /// 连接或 LISTEN 失败时按固定间隔重试，期间本副本只能收到自身发布的事件。
+    pub fn spawn_pg_listener(&self) {
+        if !self.pg_notify {
+            return;
+        }
+        let pool = self.pool.clone();
+        let sender = self.sender.clone();
+        let origin = self.origin;
+        tokio::spawn(async move {
+            let mut listener = loop {
+                match PgListener::connect_with(&pool).await {
+                    Ok(mut l) => match l.listen(NOTIFY_CHANNEL).await {
+                        Ok(_) => break l,
+                        Err(e) => tracing::error!("!!! LISTEN {} 失败: {}", NOTIFY_CHANNEL, e),
+                    },
+                    Err(e) => tracing::error!("!!! 事件监听连接失败: {}", e),
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            };
+            tracing::info!("事件监听已启动: LISTEN {}", NOTIFY_CHANNEL);
+
+            loop {
+                // recv 在连接断开时会自动重连
+                match listener.recv().await {
+                    Ok(notification) => match serde_json::from_str::<Envelope<DomainEvent>>(notification.payload()) {
+                        // 本副本发布时已在本地广播过
+                        Ok(envelope) if envelope.origin == origin => {}
+                        Ok(envelope) => {
+                            let _ = sender.send(envelope.event);
+                        }
+                        Err(e) => tracing::warn!("--- 无法解析事件载荷: {}", e),
+                    },
+                    Err(e) => {
+                        tracing::error!("!!! 事件监听异常: {}", e);
+                        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                    }
+                }
+            }
+        });
+    }
+}
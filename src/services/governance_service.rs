@@ -0,0 +1,86 @@
+use once_cell::sync::Lazy;
+use std::env;
+
+use crate::models::field::{FieldGovernance, StandardField};
+
+/// 可设为必填的字段属性
+pub const ATTRIBUTES: [&str; 8] = [
+    "business_definition",
+    "calculation_rule",
+    "example_values",
+    "data_owner",
+    "data_steward",
+    "source_system",
+    "data_type",
+    "domain",
+];
+
+/// 审核为标准前必须填写的属性，`FIELD_REQUIRED_ATTRIBUTES` 逗号分隔，未知属性忽略
+static REQUIRED: Lazy<Vec<&'static str>> = Lazy::new(|| {
+    let raw = env::var("FIELD_REQUIRED_ATTRIBUTES")
+        .unwrap_or_else(|_| "business_definition,data_owner,data_steward".to_string());
+    let mut required = Vec::new();
+    for name in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        match ATTRIBUTES.iter().find(|a| **a == name) {
+            Some(attr) if !required.contains(attr) => required.push(*attr),
+            Some(_) => {}
+            None => tracing::warn!("--- FIELD_REQUIRED_ATTRIBUTES 包含未知属性: {}", name),
+        }
+    }
+    required
+});
+
+pub fn required_attributes() -> &'static [&'static str] {
+    &REQUIRED
+}
+
+fn is_blank(value: Option<&str>) -> bool {
+    value.is_none_or(|v| v.trim().is_empty())
+}
+
+/// 字段尚未填写的必填属性，为空表示可以审核为标准
+pub fn missing_attributes(field: &StandardField) -> Vec<&'static str> {
+    required_attributes()
+        .iter()
+        .copied()
+        .filter(|attr| match *attr {
+            "business_definition" => is_blank(field.business_definition.as_deref()),
+            "calculation_rule" => is_blank(field.calculation_rule.as_deref()),
+            "example_values" => field.example_values.is_empty(),
+            "data_owner" => is_blank(field.data_owner.as_deref()),
+            "data_steward" => is_blank(field.data_steward.as_deref()),
+            "source_system" => is_blank(field.source_system.as_deref()),
+            "data_type" => is_blank(field.data_type.as_deref()),
+            "domain" => is_blank(field.domain.as_deref()),
+            _ => false,
+        })
+        .collect()
+}
+
+/// 去除治理属性首尾空白，示例值去空去重
+///
+/// 提交的空字符串保留为 `Some("")`，写库时经 `NULLIF(.., '')` 存为 NULL，
+/// 以便更新时区分“未提交（保持原值）”与“清空”。
+pub fn normalize(attrs: &mut FieldGovernance) {
+    let clean = |v: &mut Option<String>| {
+        if let Some(s) = v {
+            *s = s.trim().to_string();
+        }
+    };
+    clean(&mut attrs.business_definition);
+    clean(&mut attrs.calculation_rule);
+    clean(&mut attrs.data_owner);
+    clean(&mut attrs.data_steward);
+    clean(&mut attrs.source_system);
+
+    if let Some(values) = attrs.example_values.as_mut() {
+        let mut examples: Vec<String> = Vec::new();
+        for v in values.drain(..) {
+            let v = v.trim().to_string();
+            if !v.is_empty() && !examples.contains(&v) {
+                examples.push(v);
+            }
+        }
+        *values = examples;
+    }
+}
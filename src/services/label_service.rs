@@ -0,0 +1,398 @@
+use axum::http::{HeaderMap, header};
+use serde::Serialize;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+
+use crate::models::label::{
+    ENTITY_CODE_ITEM, ENTITY_FIELD, ENTITY_ROOT, ENTITY_TYPES, LabelRecord, LabelView, LocalizedLabel, UpsertLabel,
+};
+
+/// 规范化语言标签：语言小写、地区大写、文字首字母大写（`zh_tw` → `zh-TW`，`zh-hant` → `zh-Hant`）
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let mut parts = tag.trim().split(['-', '_']);
+    let lang = parts.next()?;
+    if !(2..=3).contains(&lang.len()) || !lang.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut out = lang.to_ascii_lowercase();
+    for part in parts {
+        if part.is_empty() || part.len() > 8 || !part.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        out.push('-');
+        match part.len() {
+            2 => out.push_str(&part.to_ascii_uppercase()),
+            4 if part.chars().all(|c| c.is_ascii_alphabetic()) => {
+                out.push_str(&part[..1].to_ascii_uppercase());
+                out.push_str(&part[1..].to_ascii_lowercase());
+            }
+            _ => out.push_str(&part.to_ascii_lowercase()),
+        }
+    }
+    Some(out)
+}
+
+fn language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+/// 基准语言（简体中文）：实体本身的中文名即是，不需要多语言名称
+pub fn is_base_locale(locale: &str) -> bool {
+    let mut parts = locale.split('-');
+    parts.next() == Some("zh") && parts.all(|p| matches!(p, "Hans" | "CN" | "SG"))
+}
+
+/// 客户端的语言偏好（按 Accept-Language 权重排序，基准语言之后的项被忽略）
+#[derive(Debug, Default)]
+pub struct LocalePrefs(Vec<String>);
+
+impl LocalePrefs {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let Some(value) = headers.get(header::ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok()) else {
+            return Self::default();
+        };
+        let mut weighted: Vec<(String, f32)> = value
+            .split(',')
+            .filter_map(|item| {
+                let mut it = item.split(';');
+                let tag = normalize_locale(it.next()?)?;
+                let q = it
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+                (q > 0.0).then_some((tag, q))
+            })
+            .collect();
+        weighted.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut prefs = Vec::new();
+        for (tag, _) in weighted {
+            if is_base_locale(&tag) {
+                break;
+            }
+            if !prefs.contains(&tag) {
+                prefs.push(tag);
+            }
+        }
+        Self(prefs)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// 按偏好顺序选择：先完全匹配，再按语言匹配（`ja-JP` 可用 `ja`，`zh-HK` 可用 `zh-TW`）
+    fn pick(&self, labels: &[LabelView]) -> Option<LabelView> {
+        self.0.iter().find_map(|tag| {
+            labels
+                .iter()
+                .find(|l| &l.locale == tag)
+                .or_else(|| labels.iter().find(|l| language(&l.locale) == language(tag)))
+                .cloned()
+        })
+    }
+}
+
+/// 附带本地化名称的实体，序列化时与原对象的字段平铺在同一层
+#[derive(Debug, Serialize)]
+pub struct Localized<T> {
+    #[serde(flatten)]
+    pub item: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LabelView>,
+}
+
+/// 批量查询实体在偏好语言下的名称
+pub async fn lookup(
+    pool: &PgPool,
+    entity_type: &str,
+    ids: &[i32],
+    prefs: &LocalePrefs,
+) -> Result<HashMap<i32, LabelView>, sqlx::Error> {
+    if prefs.is_empty() || ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let languages: Vec<String> = prefs.0.iter().map(|t| language(t).to_string()).collect();
+    let rows = sqlx::query!(
+        "SELECT entity_id, locale, label, definition FROM localized_labels
+         WHERE entity_type = $1 AND entity_id = ANY($2) AND split_part(locale, '-', 1) = ANY($3)
+         ORDER BY entity_id, locale",
+        entity_type,
+        ids,
+        &languages
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut grouped: HashMap<i32, Vec<LabelView>> = HashMap::new();
+    for r in rows {
+        grouped
+            .entry(r.entity_id)
+            .or_default()
+            .push(LabelView { locale: r.locale, label: r.label, definition: r.definition });
+    }
+    Ok(grouped.into_iter().filter_map(|(id, labels)| Some((id, prefs.pick(&labels)?))).collect())
+}
+
+/// 为实体列表附加本地化名称；查询失败时只记录日志，按原样返回
+pub async fn localize<T>(
+    pool: &PgPool,
+    entity_type: &str,
+    items: Vec<T>,
+    id_of: impl Fn(&T) -> i32,
+    prefs: &LocalePrefs,
+) -> Vec<Localized<T>> {
+    let ids: Vec<i32> = items.iter().map(&id_of).collect();
+    let mut labels = lookup(pool, entity_type, &ids, prefs).await.unwrap_or_else(|e| {
+        tracing::warn!("--- 多语言名称查询失败: {}", e);
+        HashMap::new()
+    });
+    items
+        .into_iter()
+        .map(|item| {
+            let localized = labels.remove(&id_of(&item));
+            Localized { item, localized }
+        })
+        .collect()
+}
+
+/// 校验语言标签与名称，返回规范化后的 (locale, label)
+pub fn validate(locale: &str, label: &str) -> Result<(String, String), String> {
+    let locale = normalize_locale(locale).ok_or_else(|| format!("无效的语言标签: {}", locale))?;
+    if is_base_locale(&locale) {
+        return Err(format!("{} 为基准语言，请直接修改中文名称", locale));
+    }
+    let label = label.trim();
+    if label.is_empty() || label.chars().count() > 200 {
+        return Err("label 不能为空，且不超过 200 个字符".to_string());
+    }
+    Ok((locale, label.to_string()))
+}
+
+pub fn validate_entity_type(entity_type: &str) -> Result<(), String> {
+    if ENTITY_TYPES.contains(&entity_type) {
+        Ok(())
+    } else {
+        Err(format!("未知的实体类型: {}，可选: {}", entity_type, ENTITY_TYPES.join(", ")))
+    }
+}
+
+/// 实体变更后需要通知的对象
+#[derive(Debug, Clone, Copy)]
+pub enum Owner {
+    Root(i32),
+    Field(i32), // 代码值归属其字段
+}
+
+/// 查询实体是否存在，返回变更通知的归属对象
+///
+/// 实体行加 `FOR KEY SHARE` 锁：在事务中写入名称时，并发删除实体会等到事务结束，
+/// 再由删除触发器一并清理刚写入的名称，不会留下孤立记录。
+pub async fn owner_of(conn: &mut PgConnection, entity_type: &str, id: i32) -> Result<Option<Owner>, sqlx::Error> {
+    Ok(match entity_type {
+        ENTITY_ROOT => sqlx::query_scalar!("SELECT id FROM standard_word_roots WHERE id = $1 FOR KEY SHARE", id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .map(Owner::Root),
+        ENTITY_FIELD => sqlx::query_scalar!("SELECT id FROM standard_fields WHERE id = $1 FOR KEY SHARE", id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .map(Owner::Field),
+        ENTITY_CODE_ITEM => sqlx::query_scalar!("SELECT field_id FROM standard_code_items WHERE id = $1 FOR KEY SHARE", id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .map(Owner::Field),
+        _ => None,
+    })
+}
+
+/// 新增或覆盖某个语言的名称（locale 与 label 需已通过 [`validate`]）
+pub async fn upsert(
+    conn: &mut PgConnection,
+    entity_type: &str,
+    entity_id: i32,
+    locale: &str,
+    payload: &UpsertLabel,
+) -> Result<LocalizedLabel, sqlx::Error> {
+    let definition = payload.definition.as_deref().map(str::trim).filter(|d| !d.is_empty());
+    sqlx::query_as!(
+        LocalizedLabel,
+        "INSERT INTO localized_labels (entity_type, entity_id, locale, label, definition)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (entity_type, entity_id, locale) DO UPDATE SET label = EXCLUDED.label, definition = EXCLUDED.definition
+         RETURNING id, entity_type, entity_id, locale, label, definition, created_at, updated_at",
+        entity_type,
+        entity_id,
+        locale,
+        payload.label.trim(),
+        definition
+    )
+    .fetch_one(&mut *conn)
+    .await
+}
+
+/// 按业务键批量定位导入记录对应的实体，返回与输入等长的 (实体 ID, 变更通知对象)
+///
+/// 业务键与导出一致、区分大小写，按唯一约束列等值匹配：
+/// root 按 en_abbr，field 按 field_en_name，code_item 按所属字段的 field_en_name + code_value。
+/// 命中的实体行同 [`owner_of`] 一样加 `FOR KEY SHARE` 锁，直到导入事务结束。
+async fn resolve_keys(
+    conn: &mut PgConnection,
+    records: &[&LabelRecord],
+) -> Result<Vec<Option<(i32, Owner)>>, sqlx::Error> {
+    let types: Vec<String> = records.iter().map(|r| r.entity_type.clone()).collect();
+    let keys: Vec<String> = records.iter().map(|r| r.key.trim().to_string()).collect();
+    let codes: Vec<Option<String>> = records.iter().map(|r| r.code_value.clone()).collect();
+    let rows = sqlx::query!(
+        r#"WITH k AS (SELECT * FROM unnest($1::TEXT[], $2::TEXT[], $3::TEXT[]) WITH ORDINALITY AS k(entity_type, key, code_value, ord)),
+                roots AS (
+                    SELECT k.ord, r.id, r.id AS owner FROM k
+                    JOIN standard_word_roots r ON k.entity_type = 'root' AND r.en_abbr = k.key
+                    FOR KEY SHARE OF r),
+                fields AS (
+                    SELECT k.ord, f.id, f.id AS owner FROM k
+                    JOIN standard_fields f ON k.entity_type = 'field' AND f.field_en_name = k.key
+                    FOR KEY SHARE OF f),
+                codes AS (
+                    SELECT k.ord, c.id, c.field_id AS owner FROM k
+                    JOIN standard_fields cf ON k.entity_type = 'code_item' AND cf.field_en_name = k.key
+                    JOIN standard_code_items c ON c.field_id = cf.id AND c.code_value = k.code_value
+                    FOR KEY SHARE OF c)
+           SELECT ord as "ord!", id as "id!", owner as "owner!" FROM roots
+           UNION ALL SELECT ord, id, owner FROM fields
+           UNION ALL SELECT ord, id, owner FROM codes"#,
+        &types,
+        &keys,
+        &codes as &[Option<String>]
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut resolved = vec![None; records.len()];
+    for row in rows {
+        let (id, owner) = (row.id, row.owner);
+        let owner = if records[row.ord as usize - 1].entity_type == ENTITY_ROOT {
+            Owner::Root(owner)
+        } else {
+            Owner::Field(owner)
+        };
+        resolved[row.ord as usize - 1] = Some((id, owner));
+    }
+    Ok(resolved)
+}
+
+/// 导入结果
+#[derive(Debug, Default, Serialize)]
+pub struct LabelImportResult {
+    pub success_count: usize,
+    pub failure_count: usize,
+    pub errors: Vec<String>,
+    #[serde(skip)]
+    pub owners: Vec<Owner>,
+}
+
+/// 批量导入多语言名称（已存在则覆盖）
+///
+/// 校验不通过或业务键找不到实体的记录计入 `errors`，不影响其余记录；
+/// 其余记录在同一事务中一次写入（同一实体同一语言出现多次时以最后一条为准），数据库出错则整体回滚。
+pub async fn import(pool: &PgPool, records: &[LabelRecord]) -> Result<LabelImportResult, sqlx::Error> {
+    let mut result = LabelImportResult::default();
+    let mut errors: Vec<(usize, String)> = Vec::new();
+    let mut valid: Vec<(usize, &LabelRecord, String, String)> = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        let row = index + 1;
+        if let Err(msg) = validate_entity_type(&record.entity_type) {
+            errors.push((row, msg));
+            continue;
+        }
+        match validate(&record.locale, &record.label) {
+            Ok((locale, label)) => valid.push((row, record, locale, label)),
+            Err(msg) => errors.push((row, msg)),
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+    let targets: Vec<&LabelRecord> = valid.iter().map(|(_, record, _, _)| *record).collect();
+    let resolved = resolve_keys(&mut tx, &targets).await?;
+
+    let mut latest: HashMap<(&str, i32, &str), usize> = HashMap::new();
+    let mut writes: Vec<(&str, i32, &str, &str, Option<&str>)> = Vec::new();
+    for ((row, record, locale, label), target) in valid.iter().zip(resolved) {
+        let Some((id, owner)) = target else {
+            errors.push((*row, format!("未找到 {} [{}]", record.entity_type, record.key)));
+            continue;
+        };
+        let definition = record.definition.as_deref().map(str::trim).filter(|d| !d.is_empty());
+        let write = (record.entity_type.as_str(), id, locale.as_str(), label.as_str(), definition);
+        match latest.get(&(write.0, id, write.2)) {
+            Some(&i) => writes[i] = write,
+            None => {
+                latest.insert((write.0, id, write.2), writes.len());
+                writes.push(write);
+            }
+        }
+        result.owners.push(owner);
+        result.success_count += 1;
+    }
+
+    if !writes.is_empty() {
+        let types: Vec<&str> = writes.iter().map(|w| w.0).collect();
+        let ids: Vec<i32> = writes.iter().map(|w| w.1).collect();
+        let locales: Vec<&str> = writes.iter().map(|w| w.2).collect();
+        let labels: Vec<&str> = writes.iter().map(|w| w.3).collect();
+        let definitions: Vec<Option<&str>> = writes.iter().map(|w| w.4).collect();
+        sqlx::query!(
+            "INSERT INTO localized_labels (entity_type, entity_id, locale, label, definition)
+             SELECT * FROM unnest($1::TEXT[], $2::INT[], $3::TEXT[], $4::TEXT[], $5::TEXT[])
+             ON CONFLICT (entity_type, entity_id, locale) DO UPDATE SET label = EXCLUDED.label, definition = EXCLUDED.definition",
+            &types as &[&str],
+            &ids,
+            &locales as &[&str],
+            &labels as &[&str],
+            &definitions as &[Option<&str>]
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    errors.sort_by_key(|(row, _)| *row);
+    result.errors = errors.into_iter().map(|(row, msg)| format!("行 {}: {}", row, msg)).collect();
+    result.failure_count = result.errors.len();
+    Ok(result)
+}
+
+/// 导出多语言名称（以业务键定位实体），格式与导入一致
+pub async fn export(
+    pool: &PgPool,
+    entity_type: Option<&str>,
+    locale: Option<&str>,
+) -> Result<Vec<LabelRecord>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT l.entity_type, COALESCE(r.en_abbr, f.field_en_name, cf.field_en_name) as "key!",
+                  c.code_value as "code_value?", l.locale, l.label, l.definition
+           FROM localized_labels l
+           LEFT JOIN standard_word_roots r ON l.entity_type = 'root' AND r.id = l.entity_id
+           LEFT JOIN standard_fields f ON l.entity_type = 'field' AND f.id = l.entity_id
+           LEFT JOIN standard_code_items c ON l.entity_type = 'code_item' AND c.id = l.entity_id
+           LEFT JOIN standard_fields cf ON cf.id = c.field_id
+           WHERE ($1::TEXT IS NULL OR l.entity_type = $1) AND ($2::TEXT IS NULL OR l.locale = $2)
+             AND COALESCE(r.id, f.id, c.id) IS NOT NULL
+           ORDER BY l.entity_type, 2, 3, l.locale"#,
+        entity_type,
+        locale
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| LabelRecord {
+            entity_type: r.entity_type,
+            key: r.key,
+            code_value: r.code_value,
+            locale: r.locale,
+            label: r.label,
+            definition: r.definition,
+        })
+        .collect())
+}
@@ -0,0 +1,318 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+use crate::models::lint::{LINT_SCHEDULED, LintReport};
+
+/// 保留的报告数量，超出的旧报告在写入新报告时删除
+const KEEP_REPORTS: i64 = 50;
+
+/// 定时检查使用的事务级 advisory lock 键，多副本同一时刻只有一个在运行
+const SCHEDULE_LOCK_KEY: i64 = 0x6c69_6e74; // "lint"
+
+/// 定时检查的间隔（小时），`LINT_INTERVAL_HOURS` 未设置或为 0 时不定时运行
+static INTERVAL_HOURS: Lazy<u64> = Lazy::new(|| {
+    env::var("LINT_INTERVAL_HOURS").ok().and_then(|v| v.parse().ok()).unwrap_or(0)
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,   // 数据错误，会导致生成或解析出错
+    Warning, // 存在歧义或不一致，建议处理
+    Info,    // 提示
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Consistency, // 同一概念的取值不一致
+    Ambiguity,   // 切分或解析时可能产生歧义
+    Usage,       // 使用情况
+}
+
+/// 一条检查结果
+#[derive(Debug, Serialize)]
+pub struct Finding {
+    pub check: &'static str,
+    pub category: Category,
+    pub severity: Severity,
+    pub message: String,
+    pub root_ids: Vec<i32>,
+    pub field_ids: Vec<i32>,
+}
+
+impl Finding {
+    fn new(check: &'static str, category: Category, severity: Severity, message: String) -> Self {
+        Self { check, category, severity, message, root_ids: vec![], field_ids: vec![] }
+    }
+
+    fn roots(mut self, ids: Vec<i32>) -> Self {
+        self.root_ids = ids;
+        self
+    }
+
+    fn fields(mut self, ids: Vec<i32>) -> Self {
+        self.field_ids = ids;
+        self
+    }
+}
+
+/// 中文名相同、缩写不同的词根
+async fn duplicate_cn_name(pool: &PgPool) -> Result<Vec<Finding>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT cn_name, array_agg(id ORDER BY id) as "ids!", array_agg(en_abbr ORDER BY id) as "abbrs!"
+           FROM standard_word_roots GROUP BY cn_name HAVING count(*) > 1 ORDER BY cn_name"#
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let message = format!("中文名「{}」对应多个缩写: {}", r.cn_name, r.abbrs.join(", "));
+            Finding::new("duplicate_cn_name", Category::Consistency, Severity::Warning, message).roots(r.ids)
+        })
+        .collect())
+}
+
+/// 同一个同义词属于多个词根（含与其他词根中文名相同的同义词）
+async fn shared_synonym(pool: &PgPool) -> Result<Vec<Finding>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"WITH terms AS (
+               SELECT r.id, t.term, false as is_name
+               FROM standard_word_roots r,
+                 unnest(regexp_split_to_array(COALESCE(r.associated_terms, ''), '[\s,，]+')) AS t(term)
+               WHERE t.term <> ''
+               UNION
+               SELECT id, cn_name, true FROM standard_word_roots)
+           SELECT term as "term!", array_agg(DISTINCT id) as "ids!"
+           FROM terms GROUP BY term
+           HAVING count(DISTINCT id) > 1 AND bool_or(NOT is_name)
+           ORDER BY term"#
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let message = format!("同义词「{}」被 {} 个词根同时使用，匹配时无法区分", r.term, r.ids.len());
+            Finding::new("shared_synonym", Category::Ambiguity, Severity::Warning, message).roots(r.ids)
+        })
+        .collect())
+}
+
+/// 按 `_` 分词后互为前缀的缩写（如 cust 与 cust_no）
+async fn prefix_abbr(pool: &PgPool) -> Result<Vec<Finding>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT a.id as a_id, a.en_abbr as a_abbr, b.id as b_id, b.en_abbr as b_abbr
+           FROM standard_word_roots a
+           JOIN standard_word_roots b ON starts_with(lower(b.en_abbr), lower(a.en_abbr) || '_')
+           ORDER BY a.en_abbr, b.en_abbr"#
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let message = format!("缩写 {} 是 {} 的前缀，物理名按最长匹配解析时可能产生歧义", r.a_abbr, r.b_abbr);
+            Finding::new("prefix_abbr", Category::Ambiguity, Severity::Warning, message).roots(vec![r.a_id, r.b_id])
+        })
+        .collect())
+}
+
+/// 未被任何标准字段使用的词根
+async fn unused_root(pool: &PgPool) -> Result<Vec<Finding>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT r.id, r.cn_name, r.en_abbr FROM standard_word_roots r
+           WHERE NOT EXISTS (SELECT 1 FROM standard_fields f WHERE r.id = ANY(f.composition_ids))
+           ORDER BY r.id"#
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let message = format!("词根「{}」({}) 未被任何标准字段使用", r.cn_name, r.en_abbr);
+            Finding::new("unused_root", Category::Usage, Severity::Info, message).roots(vec![r.id])
+        })
+        .collect())
+}
+
+/// 组成中引用了不存在词根的字段
+async fn dangling_composition(pool: &PgPool) -> Result<Vec<Finding>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT f.id, f.field_cn_name, array_agg(c.id ORDER BY c.ord) as "missing!"
+           FROM standard_fields f, unnest(f.composition_ids) WITH ORDINALITY AS c(id, ord)
+           WHERE NOT EXISTS (SELECT 1 FROM standard_word_roots r WHERE r.id = c.id)
+           GROUP BY f.id, f.field_cn_name ORDER BY f.id"#
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let missing: Vec<String> = r.missing.iter().map(i32::to_string).collect();
+            let message = format!("字段「{}」的组成引用了不存在的词根: {}", r.field_cn_name, missing.join(", "));
+            Finding::new("dangling_composition", Category::Consistency, Severity::Error, message).fields(vec![r.id])
+        })
+        .collect())
+}
+
+/// 英文名与组成词根缩写拼接结果不一致的字段
+async fn en_name_mismatch(pool: &PgPool) -> Result<Vec<Finding>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT f.id, f.field_cn_name, f.field_en_name, e.expected as "expected!", e.root_ids as "root_ids!"
+           FROM standard_fields f
+           CROSS JOIN LATERAL (
+               SELECT string_agg(r.en_abbr, '_' ORDER BY c.ord) as expected, array_agg(r.id ORDER BY c.ord) as root_ids,
+                      count(r.id) as found
+               FROM unnest(f.composition_ids) WITH ORDINALITY AS c(id, ord)
+               LEFT JOIN standard_word_roots r ON r.id = c.id) e
+           WHERE cardinality(f.composition_ids) > 0
+             AND e.found = cardinality(f.composition_ids)
+             AND lower(f.field_en_name) <> lower(e.expected)
+           ORDER BY f.id"#
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let message = format!(
+                "字段「{}」的英文名 {} 与组成词根拼接结果 {} 不一致",
+                r.field_cn_name, r.field_en_name, r.expected
+            );
+            Finding::new("en_name_mismatch", Category::Consistency, Severity::Warning, message)
+                .roots(r.root_ids)
+                .fields(vec![r.id])
+        })
+        .collect())
+}
+
+/// 组成完全相同的未废弃字段
+async fn duplicate_composition(pool: &PgPool) -> Result<Vec<Finding>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT array_agg(id ORDER BY id) as "ids!", array_agg(field_cn_name ORDER BY id) as "names!"
+           FROM standard_fields
+           WHERE NOT COALESCE(is_deprecated, false) AND cardinality(composition_ids) > 0
+           GROUP BY composition_ids HAVING count(*) > 1"#
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let message = format!("字段 {} 的词根组成完全相同", r.names.join("、"));
+            Finding::new("duplicate_composition", Category::Consistency, Severity::Warning, message).fields(r.ids)
+        })
+        .collect())
+}
+
+/// 运行全部检查，按严重程度排序
+pub async fn run_checks(pool: &PgPool) -> Result<Vec<Finding>, sqlx::Error> {
+    let mut findings = Vec::new();
+    findings.extend(dangling_composition(pool).await?);
+    findings.extend(duplicate_cn_name(pool).await?);
+    findings.extend(shared_synonym(pool).await?);
+    findings.extend(prefix_abbr(pool).await?);
+    findings.extend(en_name_mismatch(pool).await?);
+    findings.extend(duplicate_composition(pool).await?);
+    findings.extend(unused_root(pool).await?);
+    findings.sort_by_key(|f| f.severity);
+    Ok(findings)
+}
+
+/// 运行检查并保存报告
+pub async fn run_and_store(pool: &PgPool, trigger: &str) -> Result<LintReport, sqlx::Error> {
+    let findings = run_checks(pool).await?;
+    let count = |s: Severity| findings.iter().filter(|f| f.severity == s).count() as i32;
+    let (errors, warnings, infos) = (count(Severity::Error), count(Severity::Warning), count(Severity::Info));
+    let json = serde_json::to_value(&findings).unwrap_or_default();
+
+    let report = sqlx::query_as!(
+        LintReport,
+        r#"INSERT INTO lint_reports (trigger, error_count, warning_count, info_count, findings)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id, trigger, error_count, warning_count, info_count, findings, created_at"#,
+        trigger,
+        errors,
+        warnings,
+        infos,
+        json
+    )
+    .fetch_one(pool)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM lint_reports WHERE id NOT IN (SELECT id FROM lint_reports ORDER BY id DESC LIMIT $1)",
+        KEEP_REPORTS
+    )
+    .execute(pool)
+    .await?;
+    Ok(report)
+}
+
+/// 定时检查的一次运行；返回 None 表示本轮由其他副本负责或已有足够新的定时报告
+///
+/// 持有事务级 advisory lock 期间检查最近一份定时报告的时间，早于 `min_age_secs` 才运行，
+/// 因此多副本部署时每个间隔只保存一份定时报告。锁随事务结束释放。
+async fn run_scheduled(pool: &PgPool, min_age_secs: f64) -> Result<Option<LintReport>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let locked = sqlx::query_scalar!(r#"SELECT pg_try_advisory_xact_lock($1) as "locked!""#, SCHEDULE_LOCK_KEY)
+        .fetch_one(&mut *tx)
+        .await?;
+    if !locked {
+        return Ok(None);
+    }
+    let recent = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM lint_reports
+                         WHERE trigger = $1 AND created_at > NOW() - make_interval(secs => $2)) as "recent!""#,
+        LINT_SCHEDULED,
+        min_age_secs
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if recent {
+        return Ok(None);
+    }
+    let report = run_and_store(pool, LINT_SCHEDULED).await?;
+    tx.commit().await?;
+    Ok(Some(report))
+}
+
+/// 启动定时检查任务；未配置 `LINT_INTERVAL_HOURS` 时不启动
+///
+/// 首次检查在启动一个间隔之后运行，不在启动时立即运行。
+pub fn spawn_worker(pool: PgPool) {
+    let hours = *INTERVAL_HOURS;
+    if hours == 0 {
+        tracing::info!("未配置 LINT_INTERVAL_HOURS，词典定时检查已关闭");
+        return;
+    }
+
+    tokio::spawn(async move {
+        tracing::info!("词典定时检查已启动: 每 {} 小时", hours);
+        let period = Duration::from_secs(hours * 3600);
+        let mut ticker = tokio::time::interval(period);
+        // interval 的第一次 tick 立即完成，跳过它
+        ticker.tick().await;
+        // 各副本的计时有偏差，留出余量，避免上一份报告恰好未满一个间隔而跳过本轮
+        let min_age_secs = period.as_secs_f64() * 0.9;
+        loop {
+            ticker.tick().await;
+            match run_scheduled(&pool, min_age_secs).await {
+                Ok(Some(r)) => tracing::info!(
+                    "<<< 词典定时检查完成: 报告={}, 错误={}, 警告={}, 提示={}",
+                    r.id,
+                    r.error_count,
+                    r.warning_count,
+                    r.info_count
+                ),
+                Ok(None) => tracing::debug!("词典定时检查: 本轮已由其他副本完成，跳过"),
+                Err(e) => tracing::error!("!!! 词典定时检查失败: {}", e),
+            }
+        }
+    });
+}
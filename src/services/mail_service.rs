@@ -0,0 +1,441 @@
+use lettre::message::{Mailbox, header::ContentType};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::AppState;
+use crate::models::mail::{MAIL_FAILED, MAIL_PENDING, MAIL_SENT};
+use crate::services::event_service::DomainEvent;
+
+/// 单封邮件的最大尝试次数，超过后标记为 FAILED
+const MAX_ATTEMPTS: i32 = 6;
+/// 指数退避：第 n 次失败后等待 BASE * 2^(n-1) 秒，最长 MAX 秒
+const BASE_BACKOFF_SECS: f64 = 60.0;
+const MAX_BACKOFF_SECS: f64 = 3600.0;
+/// 队列轮询间隔与每轮最多发送数量
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+const BATCH_SIZE: usize = 20;
+/// 单次 SMTP 会话超时
+const SEND_TIMEOUT: Duration = Duration::from_secs(15);
+/// 领取后的租约时长，发送进程异常退出时超过租约的邮件会被重新领取。
+/// 每次只领取一封，租约只需覆盖单次发送超时
+const LEASE_SECS: f64 = 120.0;
+
+/// SMTP 连接配置，未设置 `SMTP_HOST` 时邮件通知整体关闭
+///
+/// - `SMTP_PORT`：默认按加密方式取 25 / 587 / 465
+/// - `SMTP_TLS`：`none`（明文，如本地 MailHog）/ `starttls`（默认）/ `tls`
+/// - `SMTP_USERNAME` / `SMTP_PASSWORD`：可选认证
+/// - `SMTP_FROM`：发件人，默认 `data-dict@localhost`
+struct SmtpConfig {
+    host: String,
+    port: Option<u16>,
+    tls: String,
+    credentials: Option<Credentials>,
+    from: String,
+}
+
+static SMTP: Lazy<Option<SmtpConfig>> = Lazy::new(|| {
+    let host = env::var("SMTP_HOST").ok().filter(|h| !h.is_empty())?;
+    let credentials = match (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD")) {
+        (Ok(user), Ok(pass)) if !user.is_empty() => Some(Credentials::new(user, pass)),
+        _ => None,
+    };
+    Some(SmtpConfig {
+        host,
+        port: env::var("SMTP_PORT").ok().and_then(|p| p.parse().ok()),
+        tls: env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string()).to_lowercase(),
+        credentials,
+        from: env::var("SMTP_FROM").unwrap_or_else(|_| "data-dict@localhost".to_string()),
+    })
+});
+
+/// 解析发件人并建立 SMTP 传输；配置有误时记录日志并关闭邮件通知，不影响服务启动
+static MAILER: Lazy<Option<(Mailbox, AsyncSmtpTransport<Tokio1Executor>)>> = Lazy::new(|| {
+    let config = SMTP.as_ref()?;
+    let from: Mailbox = match config.from.parse() {
+        Ok(from) => from,
+        Err(e) => {
+            tracing::error!("!!! SMTP_FROM 格式错误 [{}]，邮件通知已关闭: {}", config.from, e);
+            return None;
+        }
+    };
+    match build_transport(config) {
+        Ok(transport) => Some((from, transport)),
+        Err(e) => {
+            tracing::error!("!!! SMTP 配置错误，邮件通知已关闭: {}", e);
+            None
+        }
+    }
+});
+
+pub fn enabled() -> bool {
+    MAILER.is_some()
+}
+
+fn build_transport(config: &SmtpConfig) -> Result<AsyncSmtpTransport<Tokio1Executor>, String> {
+    let (builder, default_port) = match config.tls.as_str() {
+        "none" => (AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host), 25),
+        "tls" => (
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host).map_err(|e| e.to_string())?,
+            465,
+        ),
+        _ => (
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host).map_err(|e| e.to_string())?,
+            587,
+        ),
+    };
+    let mut builder = builder
+        .port(config.port.unwrap_or(default_port))
+        .timeout(Some(SEND_TIMEOUT));
+    if let Some(credentials) = &config.credentials {
+        builder = builder.credentials(credentials.clone());
+    }
+    Ok(builder.build())
+}
+
+// ---------------------------------------------------------------------------
+// 邮件模板（zh / en）
+// ---------------------------------------------------------------------------
+
+/// 单封邮件的主题与正文模板，`{name}` 占位符在渲染时替换
+#[derive(Deserialize)]
+struct MailTemplate {
+    subject: String,
+    body: String,
+}
+
+/// 一种语言的全部模板，对应 `templates/mail/<locale>.json`
+#[derive(Deserialize)]
+struct LocaleTemplates {
+    task_types: HashMap<String, String>,
+    outcomes: HashMap<String, String>,
+    footer: String,
+    task_submitted: MailTemplate,
+    task_resolved: MailTemplate,
+}
+
+/// 内置模板随二进制发布；设置 `MAIL_TEMPLATE_DIR` 后优先读取该目录下的 `zh.json` / `en.json`
+static TEMPLATES: Lazy<[LocaleTemplates; 2]> = Lazy::new(|| {
+    [
+        load_templates("zh", include_str!("../../templates/mail/zh.json")),
+        load_templates("en", include_str!("../../templates/mail/en.json")),
+    ]
+});
+
+fn load_templates(locale: &str, builtin: &str) -> LocaleTemplates {
+    let custom = env::var("MAIL_TEMPLATE_DIR").ok().filter(|d| !d.is_empty()).and_then(|dir| {
+        let path = Path::new(&dir).join(format!("{}.json", locale));
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(templates) => Some(templates),
+            Err(e) => {
+                tracing::error!("!!! 邮件模板 {} 加载失败，使用内置模板: {}", path.display(), e);
+                None
+            }
+        }
+    });
+    custom.unwrap_or_else(|| serde_json::from_str(builtin).expect("内置邮件模板格式错误"))
+}
+
+fn templates(locale: &str) -> &'static LocaleTemplates {
+    if locale.starts_with("en") { &TEMPLATES[1] } else { &TEMPLATES[0] }
+}
+
+/// 依次替换 `{name}` 占位符
+fn fill(template: &str, vars: &[(&str, &str)]) -> String {
+    vars.iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+impl LocaleTemplates {
+    fn task_type_label<'a>(&'a self, task_type: &'a str) -> &'a str {
+        self.task_types.get(task_type).map_or(task_type, String::as_str)
+    }
+
+    fn render(&self, template: &MailTemplate, vars: &[(&str, &str)]) -> (String, String) {
+        (fill(&template.subject, vars), fill(&template.body, vars) + &self.footer)
+    }
+}
+
+/// 申请的简要描述：字段/词根中文名，其次为字段 ID
+fn task_summary(payload: &serde_json::Value) -> String {
+    ["field_cn_name", "cn_name", "code_name"]
+        .iter()
+        .find_map(|k| payload[*k].as_str().map(str::to_string))
+        .or_else(|| payload["field_id"].as_i64().map(|id| format!("#{}", id)))
+        .unwrap_or_default()
+}
+
+fn render_task_submitted(locale: &str, task_id: i32, task_type: &str, summary: &str) -> (String, String) {
+    let t = templates(locale);
+    let task_id = task_id.to_string();
+    // summary 来自用户输入，最后替换，避免其中的占位符被展开
+    t.render(
+        &t.task_submitted,
+        &[("task_id", &task_id), ("task_type", t.task_type_label(task_type)), ("summary", summary)],
+    )
+}
+
+fn render_task_resolved(
+    locale: &str,
+    task_id: i32,
+    task_type: &str,
+    summary: &str,
+    status: &str,
+) -> (String, String) {
+    let t = templates(locale);
+    let task_id = task_id.to_string();
+    let outcome = t.outcomes.get(status).map_or(status, String::as_str);
+    t.render(
+        &t.task_resolved,
+        &[
+            ("task_id", &task_id),
+            ("task_type", t.task_type_label(task_type)),
+            ("outcome", outcome),
+            ("summary", summary),
+        ],
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 入队
+// ---------------------------------------------------------------------------
+
+async fn enqueue(pool: &PgPool, recipient: &str, (subject, body): (String, String)) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO mail_outbox (recipient, subject, body) VALUES ($1, $2, $3)",
+        recipient,
+        subject,
+        body
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 新申请通知所有留有邮箱且未退订的管理员
+async fn enqueue_task_submitted(pool: &PgPool, task_id: i32) -> Result<usize, sqlx::Error> {
+    let Some(task) = sqlx::query!("SELECT task_type, payload FROM notification_tasks WHERE id = $1", task_id)
+        .fetch_optional(pool)
+        .await?
+    else {
+        return Ok(0);
+    };
+    let summary = task_summary(&task.payload.unwrap_or_default());
+
+    let admins = sqlx::query!(
+        r#"SELECT email as "email!", locale FROM users
+           WHERE role = 'admin' AND email IS NOT NULL AND email <> '' AND NOT email_opt_out"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for admin in &admins {
+        let mail = render_task_submitted(&admin.locale, task_id, &task.task_type, &summary);
+        enqueue(pool, &admin.email, mail).await?;
+    }
+    Ok(admins.len())
+}
+
+/// 申请处理完成后通知申请人
+async fn enqueue_task_resolved(pool: &PgPool, task_ids: &[i32], status: &str) -> Result<usize, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT t.id, t.task_type, t.payload, u.email as "email!", u.locale
+           FROM notification_tasks t
+           JOIN users u ON u.id = t.requester_id
+           WHERE t.id = ANY($1) AND u.email IS NOT NULL AND u.email <> '' AND NOT u.email_opt_out"#,
+        task_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for row in &rows {
+        let summary = task_summary(&row.payload.clone().unwrap_or_default());
+        let mail = render_task_resolved(&row.locale, row.id, &row.task_type, &summary, status);
+        enqueue(pool, &row.email, mail).await?;
+    }
+    Ok(rows.len())
+}
+
+/// 根据任务事件生成通知邮件并写入发件箱
+///
+/// 只写数据库、不连接 SMTP，邮件服务不可用不会影响提交/审批本身。
+async fn enqueue_for_event(pool: &PgPool, event: &DomainEvent) {
+    let res = match event {
+        DomainEvent::TaskSubmitted { task_id, .. } => enqueue_task_submitted(pool, *task_id).await,
+        DomainEvent::TaskResolved { task_ids, status } => enqueue_task_resolved(pool, task_ids, status).await,
+        _ => return,
+    };
+    match res {
+        Ok(0) => {}
+        Ok(n) => tracing::debug!("通知邮件入队: event={}, 数量={}", event.name(), n),
+        Err(e) => tracing::error!("!!! 通知邮件入队失败: {}", e),
+    }
+}
+
+/// 订阅本副本发布的任务事件并写入发件箱，未配置 SMTP 时不启动
+///
+/// 只处理本副本发布的事件，多副本部署时每个事件只入队一次。
+pub fn spawn_enqueuer(state: &AppState) {
+    if !enabled() {
+        return;
+    }
+    let pool = state.db.clone();
+    let mut rx = state.events.subscribe_local();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => enqueue_for_event(&pool, &event).await,
+                Err(RecvError::Lagged(n)) => tracing::error!("!!! 通知邮件入队积压，丢失 {} 个事件", n),
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+// ---------------------------------------------------------------------------
+// 发送
+// ---------------------------------------------------------------------------
+
+/// 发送失败类型：临时错误可重试，永久错误（地址无效、被拒收）直接标记失败
+enum SendError {
+    Transient(String),
+    Permanent(String),
+}
+
+async fn send(
+    transport: &AsyncSmtpTransport<Tokio1Executor>,
+    from: &Mailbox,
+    recipient: &str,
+    subject: &str,
+    body: &str,
+) -> Result<(), SendError> {
+    let to: Mailbox = recipient
+        .parse()
+        .map_err(|e| SendError::Permanent(format!("收件地址无效: {}", e)))?;
+    let message = Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body.to_string())
+        .map_err(|e| SendError::Permanent(e.to_string()))?;
+
+    match transport.send(message).await {
+        Ok(_) => Ok(()),
+        Err(e) if e.is_permanent() => Err(SendError::Permanent(e.to_string())),
+        Err(e) => Err(SendError::Transient(e.to_string())),
+    }
+}
+
+fn backoff_secs(attempts: i32) -> f64 {
+    (BASE_BACKOFF_SECS * 2f64.powi(attempts.max(1) - 1)).min(MAX_BACKOFF_SECS)
+}
+
+async fn record_attempt(
+    pool: &PgPool,
+    mail_id: i64,
+    attempts: i32,
+    outcome: Result<(), SendError>,
+) -> Result<(), sqlx::Error> {
+    let (status, error) = match outcome {
+        Ok(()) => {
+            sqlx::query!(
+                "UPDATE mail_outbox SET status = $1, attempts = $2, last_error = NULL, sent_at = NOW() WHERE id = $3",
+                MAIL_SENT,
+                attempts,
+                mail_id
+            )
+            .execute(pool)
+            .await?;
+            return Ok(());
+        }
+        Err(SendError::Transient(e)) if attempts < MAX_ATTEMPTS => (MAIL_PENDING, e),
+        Err(SendError::Transient(e)) | Err(SendError::Permanent(e)) => (MAIL_FAILED, e),
+    };
+    tracing::warn!("--- 通知邮件发送失败: id={}, 第 {} 次, {}", mail_id, attempts, error);
+
+    sqlx::query!(
+        "UPDATE mail_outbox
+         SET status = $1, attempts = $2, last_error = $3, next_attempt_at = NOW() + make_interval(secs => $4)
+         WHERE id = $5",
+        status,
+        attempts,
+        error,
+        backoff_secs(attempts),
+        mail_id
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 逐封领取到期邮件并发送，每轮最多 `BATCH_SIZE` 封
+///
+/// `FOR UPDATE SKIP LOCKED` + 租约，多副本不会重复发送；每次只领取一封，
+/// 避免同批后续邮件在等待前序发送超时期间租约过期被其他副本重复领取。
+async fn process_due(
+    pool: &PgPool,
+    transport: &AsyncSmtpTransport<Tokio1Executor>,
+    from: &Mailbox,
+) -> Result<usize, sqlx::Error> {
+    let mut sent = 0;
+    while sent < BATCH_SIZE {
+        let due = sqlx::query!(
+            r#"UPDATE mail_outbox SET next_attempt_at = NOW() + make_interval(secs => $1)
+               WHERE id = (
+                   SELECT id FROM mail_outbox
+                   WHERE status = $2 AND next_attempt_at <= NOW()
+                   ORDER BY next_attempt_at
+                   LIMIT 1
+                   FOR UPDATE SKIP LOCKED)
+               RETURNING id, recipient, subject, body, attempts"#,
+            LEASE_SECS,
+            MAIL_PENDING
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        let Some(mail) = due else { break };
+        let outcome = send(transport, from, &mail.recipient, &mail.subject, &mail.body).await;
+        record_attempt(pool, mail.id, mail.attempts + 1, outcome).await?;
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+/// 启动后台发信任务；未配置 SMTP 或配置有误时不启动
+pub fn spawn_worker(pool: PgPool) {
+    let Some(config) = SMTP.as_ref() else {
+        tracing::info!("未配置 SMTP_HOST，邮件通知已关闭");
+        return;
+    };
+    let Some((from, transport)) = MAILER.as_ref() else {
+        return;
+    };
+    // 启动时即加载模板，便于及早发现自定义模板的错误
+    Lazy::force(&TEMPLATES);
+
+    tokio::spawn(async move {
+        tracing::info!("邮件发送任务已启动: {}:{}", config.host, config.port.map_or("默认".to_string(), |p| p.to_string()));
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match process_due(&pool, transport, from).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("<<< 本轮发送通知邮件 {} 封", n),
+                Err(e) => tracing::error!("!!! 邮件发件箱处理异常: {}", e),
+            }
+        }
+    });
+}
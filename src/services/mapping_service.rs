@@ -1,78 +1,540 @@
 use sqlx::PgPool;
+use crate::AppState;
 use crate::models::word_root::WordRoot;
+use crate::services::segment_service::{self, Segmenter};
+use crate::services::stopword_service::{self, IgnoredToken};
+use crate::services::{pinyin_service, vector_service};
+use crate::services::search_service::{self, FieldMatch, ScoreBreakdown};
+use once_cell::sync::Lazy;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::env;
 
 #[derive(Serialize)]
 pub struct Segment {
-    pub word: String,              // 原始切分的词
-    pub candidates: Vec<WordRoot>, // 匹配到的所有候选词根（包含名称匹配和同义词匹配）
+    pub word: String,                // 原始切分的词
+    pub candidates: Vec<Candidate>,  // 匹配到的所有候选词根，按得分降序
+    pub recommended: Option<i32>,    // 推荐的默认选择（词根 ID），得分过低时为 null
+    pub semantic_fallback: bool,     // 候选全部来自语义召回（SQL 匹配无结果）
 }
 
-pub async fn suggest_field_name(pool: &PgPool, cn_input: &str) -> Vec<Segment> {
+/// 候选词根被提出的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchReason {
+    ExactName,        // 中文名全等
+    SynonymExact,     // 与某个同义词全等
+    SynonymSubstring, // 同义词中包含该词
+    Pinyin,           // 拼音 / 首字母命中
+    Trigram,          // 中文名或同义词 pg_trgm 相似
+    Vector,           // Qdrant 语义相似
+}
+
+impl MatchReason {
+    /// 各原因的权重：原始相似度 × 权重 = 该证据的得分
+    fn weight(self) -> f32 {
+        match self {
+            MatchReason::ExactName => 1.0,
+            MatchReason::SynonymExact => 0.95,
+            MatchReason::Pinyin => 0.85,
+            MatchReason::Trigram => 0.8,
+            MatchReason::Vector => 0.75,
+            MatchReason::SynonymSubstring => 0.7,
+        }
+    }
+}
+
+/// 一条匹配证据
+#[derive(Debug, Clone, Serialize)]
+pub struct Evidence {
+    pub reason: MatchReason,
+    pub similarity: f32, // 原始相似度（全等类为 1）
+    pub score: f32,      // 加权后的得分
+}
+
+/// 候选词根及其得分说明
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    #[serde(flatten)]
+    pub root: WordRoot,
+    pub score: f32,              // 最高证据得分 + 其余证据的佐证加分，上限 1
+    pub reason: MatchReason,     // 得分最高的证据
+    pub evidence: Vec<Evidence>, // 全部证据，按得分降序
+}
+
+/// 每多一条证据的佐证加分
+const CORROBORATION_BONUS: f32 = 0.05;
+/// 推荐默认选择的最低得分
+const RECOMMEND_MIN_SCORE: f32 = 0.5;
+
+/// 分段语义召回的参数
+#[derive(Debug, Clone, Copy)]
+pub struct SemanticOptions {
+    pub top_k: u64,     // 每段召回的词根数量
+    pub min_score: f32, // 余弦相似度下限
+}
+
+/// 默认参数，可通过 `SUGGEST_SEMANTIC_TOP_K`（1~50，默认 5）与 `SUGGEST_SEMANTIC_MIN_SCORE`（0~1，默认 0.5）调整
+static SEMANTIC_DEFAULTS: Lazy<SemanticOptions> = Lazy::new(|| SemanticOptions {
+    top_k: env::var("SUGGEST_SEMANTIC_TOP_K")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| (1..=50).contains(v))
+        .unwrap_or(5),
+    min_score: env::var("SUGGEST_SEMANTIC_MIN_SCORE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| (0.0..=1.0).contains(v))
+        .unwrap_or(0.5),
+});
+
+impl Default for SemanticOptions {
+    fn default() -> Self {
+        *SEMANTIC_DEFAULTS
+    }
+}
+
+impl SemanticOptions {
+    /// 以请求参数覆盖默认值，超出范围的取值截断到合法区间
+    pub fn with(top_k: Option<u64>, min_score: Option<f32>) -> Self {
+        let defaults = Self::default();
+        Self {
+            top_k: top_k.map_or(defaults.top_k, |k| k.clamp(1, 50)),
+            min_score: min_score.map_or(defaults.min_score, |s| s.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+impl Candidate {
+    fn new(root: WordRoot) -> Self {
+        Self { root, score: 0.0, reason: MatchReason::ExactName, evidence: Vec::new() }
+    }
+
+    fn add(&mut self, reason: MatchReason, similarity: f32) {
+        self.evidence.push(Evidence { reason, similarity, score: similarity * reason.weight() });
+    }
+
+    /// 汇总证据：按得分排序，取最高者为主要原因
+    fn finish(mut self) -> Self {
+        self.evidence.sort_by(|a, b| b.score.total_cmp(&a.score));
+        if let Some(top) = self.evidence.first() {
+            self.reason = top.reason;
+            let bonus = CORROBORATION_BONUS * (self.evidence.len() - 1) as f32;
+            self.score = (top.score + bonus).min(1.0);
+        }
+        self
+    }
+}
+
+/// 将匹配好的候选排序，并给出推荐选择
+pub fn build_segment(word: String, candidates: Vec<Candidate>) -> Segment {
+    let mut candidates: Vec<Candidate> = candidates.into_iter().map(Candidate::finish).collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.root.cn_name.cmp(&b.root.cn_name)));
+    let recommended = candidates.first().filter(|c| c.score >= RECOMMEND_MIN_SCORE).map(|c| c.root.id);
+    let semantic_fallback = !candidates.is_empty()
+        && candidates.iter().all(|c| c.evidence.iter().all(|e| e.reason == MatchReason::Vector));
+    Segment { word, candidates, recommended, semantic_fallback }
+}
+
+/// 一次查询为多个词匹配候选词根（不含语义召回），返回 词 → 候选；没有候选的词不出现在结果中
+///
+/// 始终包含中文名全等、同义词全等/子串命中与拼音词条（全拼或首字母）全等；
+/// `fuzzy` 为 true 时再纳入 trigram 相似度达到阈值的词根，以容忍错别字与不完整输入。
+pub async fn match_roots_batch(
+    pool: &PgPool,
+    words: &[String],
+    fuzzy: bool,
+) -> Result<HashMap<String, Vec<Candidate>>, sqlx::Error> {
+    let pys: Vec<Option<String>> = words.iter().map(|w| pinyin_service::normalize_query(w)).collect();
+    let threshold = search_service::trgm_threshold();
+    // 连接条件中的 `=`、ILIKE、LIKE 与 `%` / `<%` 均可走索引，每个词只探测命中的词根
+    let mut tx = pool.begin().await?;
+    search_service::set_trgm_threshold(&mut tx, threshold).await?;
+    let rows = sqlx::query!(
+        r#"SELECT w.word as "word!", r.id, r.cn_name, r.en_abbr, r.en_full_name, r.associated_terms, r.remark, r.domain,
+                  r.created_at, r.updated_at,
+                  m.synonym_exact as "synonym_exact!", m.synonym_substring as "synonym_substring!",
+                  m.pinyin_exact as "pinyin_exact!", m.pinyin::REAL as "pinyin!", m.trigram::REAL as "trigram!"
+           FROM unnest($1::TEXT[], $2::TEXT[]) AS w(word, py)
+           JOIN standard_word_roots r
+             ON r.cn_name = w.word OR r.associated_terms ILIKE '%' || w.word || '%'
+             OR (r.cn_pinyin LIKE '%' || w.py || '%' AND ' ' || r.cn_pinyin || ' ' LIKE '% ' || w.py || ' %')
+             OR (r.cn_initials LIKE '%' || w.py || '%' AND ' ' || r.cn_initials || ' ' LIKE '% ' || w.py || ' %')
+             OR ($3 AND (r.cn_name % w.word OR w.word <% r.associated_terms
+                         OR w.py <% r.cn_pinyin OR w.py <% r.cn_initials))
+           CROSS JOIN LATERAL (SELECT
+                 w.word = ANY(regexp_split_to_array(COALESCE(r.associated_terms, ''), '[\s,，]+')) as synonym_exact,
+                 COALESCE(r.associated_terms ILIKE '%' || w.word || '%', false) as synonym_substring,
+                 COALESCE(' ' || r.cn_pinyin || ' ' LIKE '% ' || w.py || ' %'
+                       OR ' ' || r.cn_initials || ' ' LIKE '% ' || w.py || ' %', false) as pinyin_exact,
+                 GREATEST(COALESCE(word_similarity(w.py, r.cn_pinyin), 0), COALESCE(word_similarity(w.py, r.cn_initials), 0)) as pinyin,
+                 GREATEST(similarity(r.cn_name, w.word), COALESCE(word_similarity(w.word, r.associated_terms), 0)) as trigram) m"#,
+        words,
+        &pys as &[Option<String>],
+        fuzzy
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let mut result: HashMap<String, Vec<Candidate>> = HashMap::new();
+    for r in rows {
+        let exact_name = r.cn_name == r.word;
+        let mut c = Candidate::new(WordRoot {
+            id: r.id,
+            cn_name: r.cn_name,
+            en_abbr: r.en_abbr,
+            en_full_name: r.en_full_name,
+            associated_terms: r.associated_terms,
+            remark: r.remark,
+            domain: r.domain,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        });
+        if exact_name {
+            c.add(MatchReason::ExactName, 1.0);
+        }
+        if r.synonym_exact {
+            c.add(MatchReason::SynonymExact, 1.0);
+        } else if r.synonym_substring {
+            c.add(MatchReason::SynonymSubstring, 1.0);
+        }
+        if r.pinyin_exact {
+            c.add(MatchReason::Pinyin, 1.0);
+        } else if fuzzy && r.pinyin >= threshold {
+            c.add(MatchReason::Pinyin, r.pinyin);
+        }
+        // 中文名或同义词全等时 trigram 相似度必然为 1，不再重复计为证据
+        if fuzzy && !exact_name && !r.synonym_exact && r.trigram >= threshold {
+            c.add(MatchReason::Trigram, r.trigram);
+        }
+        result.entry(r.word).or_default().push(c);
+    }
+    Ok(result)
+}
+
+/// 语义召回：对各词一次批量检索 Qdrant `word_roots`，已有候选追加证据，新命中的词根回表后加入候选
+///
+/// `only_unmatched` 为 true 时只检索 SQL 匹配无结果的词（批量模式），否则所有词都参与，
+/// 语义近邻同时作为已有候选的佐证。向量服务异常只记录日志，回表失败返回错误。
+pub async fn add_semantic_matches(
+    state: &AppState,
+    words: &[String],
+    by_word: &mut HashMap<String, Vec<Candidate>>,
+    only_unmatched: bool,
+    options: SemanticOptions,
+) -> Result<(), sqlx::Error> {
+    let targets: Vec<String> = words
+        .iter()
+        .filter(|w| !only_unmatched || by_word.get(*w).is_none_or(Vec::is_empty))
+        .cloned()
+        .collect();
+    let hits = match vector_service::search_many(state, "word_roots", &targets, options.top_k, options.min_score).await {
+        Ok(hits) => hits,
+        Err(e) => {
+            tracing::warn!("--- 分词建议: 语义召回失败: {}", e);
+            return Ok(());
+        }
+    };
+
+    // 未出现在 SQL 候选中的词根统一回表一次
+    let mut missing: Vec<i32> = targets
+        .iter()
+        .zip(&hits)
+        .flat_map(|(w, hits)| {
+            let candidates = by_word.get(w);
+            hits.iter()
+                .map(|(id, _)| *id)
+                .filter(move |id| !candidates.is_some_and(|cs| cs.iter().any(|c| c.root.id == *id)))
+        })
+        .collect();
+    missing.sort();
+    missing.dedup();
+    let loaded = if missing.is_empty() {
+        HashMap::new()
+    } else {
+        search_service::load_roots(&state.db, &missing).await?
+    };
+
+    for (word, hits) in targets.into_iter().zip(hits) {
+        let candidates = by_word.entry(word).or_default();
+        for (id, similarity) in hits {
+            if let Some(c) = candidates.iter_mut().find(|c| c.root.id == id) {
+                c.add(MatchReason::Vector, similarity);
+            } else if let Some(root) = loaded.get(&id) {
+                let mut c = Candidate::new(root.clone());
+                c.add(MatchReason::Vector, similarity);
+                candidates.push(c);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 分词建议结果
+pub struct Suggestion {
+    pub segments: Vec<Segment>,
+    pub ignored: Vec<IgnoredToken>, // 切分后被忽略的停用词与标点
+}
+
+pub async fn suggest_field_name(
+    state: &AppState,
+    cn_input: &str,
+    segmenter: Segmenter,
+    semantic: SemanticOptions,
+) -> Result<Suggestion, sqlx::Error> {
+    let pool = &state.db;
     let input = cn_input.trim();
-    if input.is_empty() { return vec![]; }
+    if input.is_empty() { return Ok(Suggestion { segments: vec![], ignored: vec![] }); }
 
     // --- 阶段 1：全称精准/同义词匹配 ---
     // 逻辑：如果不拆分就能匹配到词根，说明这是一个完整的业务术语，优先保留。
-    let full_pattern = format!("%{}%", input);
-    let full_candidates: Vec<WordRoot> = sqlx::query_as!(
-        WordRoot,
-        r#"SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at 
-           FROM standard_word_roots 
-           WHERE cn_name = $1 
-           OR associated_terms ILIKE $2
-           ORDER BY (cn_name = $1) DESC, cn_name ASC"#,
-        input,
-        full_pattern
-    )
-    .fetch_all(pool)
-    .await
-    .unwrap_or_default();
+    // 此阶段不做模糊匹配，避免整句与某个短词根"有点像"就跳过切分。
+    let full_candidates = match_roots_batch(pool, &[input.to_string()], false).await?.remove(input).unwrap_or_default();
 
     // 如果全称匹配到了结果，直接返回单段结果，不再切分
     if !full_candidates.is_empty() {
         tracing::info!("全称匹配成功: {}", input);
-        return vec![Segment {
-            word: input.to_string(),
-            candidates: full_candidates,
-        }];
+        return Ok(Suggestion { segments: vec![build_segment(input.to_string(), full_candidates)], ignored: vec![] });
     }
 
     // --- 阶段 2：分词匹配逻辑 ---
     // 逻辑：全称没搜到，说明需要拆分组合。
     tracing::info!("全称未命中，进入分词逻辑: {}", input);
     
-    // 获取读锁
-    let jieba_read = crate::JIEBA.read().await;
-    // 使用精准模式切分中文
-    let words = jieba_read.cut(input, false);
-    
-    let mut segments = Vec::new();
-
-    for word in words {
-        let trimmed = word.trim();
-        if trimmed.is_empty() { continue; }
-        
-        let pattern = format!("%{}%", trimmed);
-
-        let candidates: Vec<WordRoot> = sqlx::query_as!(
-            WordRoot,
-            r#"SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, created_at 
-               FROM standard_word_roots 
-               WHERE cn_name = $1 
-               OR associated_terms ILIKE $2
-               ORDER BY (cn_name = $1) DESC, cn_name ASC"#,
-            trimmed,
-            pattern
-        )
-        .fetch_all(pool)
-        .await
-        .unwrap_or_default();
-
-        segments.push(Segment {
-            word: trimmed.to_string(),
-            candidates,
-        });
+    let (words, ignored) = cut_input(pool, input, segmenter).await?;
+    let mut distinct = words.clone();
+    distinct.sort();
+    distinct.dedup();
+
+    // 各段一次性模糊匹配，再做语义召回：无 SQL 候选的段由语义近邻兜底，其余段的近邻作为佐证
+    let mut by_word = match_roots_batch(pool, &distinct, true).await?;
+    add_semantic_matches(state, &distinct, &mut by_word, false, semantic).await?;
+
+    let segments = words
+        .into_iter()
+        .map(|w| {
+            let candidates = by_word.get(&w).cloned().unwrap_or_default();
+            build_segment(w, candidates)
+        })
+        .collect();
+    Ok(Suggestion { segments, ignored })
+}
+
+/// 切分输入并去掉停用词与标点，返回 (参与匹配的分段, 被忽略的分段)
+///
+/// 拼音按词根拼音词条切分；中文按词典最大覆盖切分（未覆盖片段再由 Jieba 细分），或使用 Jieba 精准模式。
+pub async fn cut_input(
+    pool: &PgPool,
+    input: &str,
+    segmenter: Segmenter,
+) -> Result<(Vec<String>, Vec<IgnoredToken>), sqlx::Error> {
+    if let Some(py) = pinyin_service::normalize_query(input) {
+        let entries = pinyin_entries(pool, std::slice::from_ref(&py)).await?;
+        return Ok((cut_pinyin(&py, &entries), vec![]));
+    }
+    if segmenter == Segmenter::Dict {
+        match segment_service::load_dictionary(pool, &[input.to_string()]).await {
+            Ok(dict) => return Ok(stopword_service::filter(segment_service::segment(input, &dict).matching_words().await)),
+            Err(e) => tracing::warn!("--- 词典切分失败，改用 Jieba: {}", e),
+        }
     }
-    segments
-}
\ No newline at end of file
+    Ok(cut_jieba(input).await)
+}
+
+/// Jieba 精准模式切分并去掉停用词与标点
+pub async fn cut_jieba(input: &str) -> (Vec<String>, Vec<IgnoredToken>) {
+    // 获取读锁，切分完成后立即释放
+    let words = {
+        let jieba_read = crate::JIEBA.read().await;
+        jieba_read.cut(input, false).into_iter().map(str::to_string).collect()
+    };
+    stopword_service::filter(words)
+}
+
+/// 词根拼音索引中作为子串出现在任一输入里的词条（全拼或首字母），多条拼音输入只查询一次
+pub async fn pinyin_entries(pool: &PgPool, pys: &[String]) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT DISTINCT e as "e!" FROM standard_word_roots r,
+             unnest(string_to_array(concat_ws(' ', r.cn_pinyin, r.cn_initials), ' ')) e
+           WHERE e <> '' AND EXISTS (SELECT 1 FROM unnest($1::TEXT[]) q WHERE strpos(q, e) > 0)"#,
+        pys
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 切分拼音输入（如 `khbh`、`kehubianhao`）
+///
+/// 从左到右取 `entries` 中最长的词条；匹配不上的字母合并为一段原样保留，交给后续的模糊匹配处理。
+pub fn cut_pinyin(py: &str, entries: &[String]) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut pending = String::new();
+    let mut rest = py;
+    while !rest.is_empty() {
+        match entries.iter().filter(|e| rest.starts_with(e.as_str())).max_by_key(|e| e.len()) {
+            Some(entry) => {
+                if !pending.is_empty() {
+                    words.push(std::mem::take(&mut pending));
+                }
+                words.push(entry.clone());
+                rest = &rest[entry.len()..];
+            }
+            None => {
+                pending.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    if !pending.is_empty() {
+        words.push(pending);
+    }
+    words
+}
+
+/// 将物理名 / 英文输入拆成小写单词
+///
+/// 非字母数字字符（`_`、`-`、空格等）作为分隔；camelCase 在小写/数字→大写处切开，
+/// 连续大写的缩写在其后接小写时切开（`HTTPCode` → `http`、`code`）；字母与数字不拆开。
+pub fn split_identifier(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for part in input.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (prev, cur) = (chars[i - 1], chars[i]);
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+            if cur.is_ascii_uppercase() && (prev.is_ascii_lowercase() || prev.is_ascii_digit() || (prev.is_ascii_uppercase() && next_lower)) {
+                tokens.push(chars[start..i].iter().collect::<String>().to_lowercase());
+                start = i;
+            }
+        }
+        if start < chars.len() {
+            tokens.push(chars[start..].iter().collect::<String>().to_lowercase());
+        }
+    }
+    tokens
+}
+
+/// 物理名中一段连续单词及其对应的词根
+#[derive(Serialize)]
+pub struct TokenMatch {
+    pub token: String,      // 以 `_` 连接的单词，如 `cust` 或 `order_no`
+    pub roots: Vec<WordRoot>, // 英文缩写或英文全称与之相同的词根，为空表示未识别
+}
+
+/// 物理名反查结果
+#[derive(Serialize)]
+pub struct ReverseMapping {
+    pub input: String,
+    pub normalized: String,              // 归一后的 snake_case 名称
+    pub tokens: Vec<TokenMatch>,
+    pub unmatched: Vec<String>,          // 未识别的单词
+    pub suggested_cn_name: Option<String>, // 全部单词都识别时，按顺序拼接首选词根中文名
+    pub fields: Vec<FieldMatch>,         // 对应的标准字段
+}
+
+/// 反查时返回的相近字段数
+const REVERSE_FIELD_LIMIT: i64 = 5;
+
+/// 将物理名（如 `cust_id`、`custId`）反查为中文标准
+///
+/// 1. 拆分为单词后，从左到右按最长连续片段匹配词根的英文缩写或英文全称（全称中的空格视作 `_`）
+/// 2. 全部识别时给出中文名建议
+/// 3. 英文名全等或词根组成完全一致的字段视为精确命中；否则按英文召回给出最相近的字段
+pub async fn reverse_map(pool: &PgPool, name: &str) -> Result<ReverseMapping, sqlx::Error> {
+    let words = split_identifier(name);
+    let normalized = words.join("_");
+
+    // 所有连续片段一次性查询
+    let mut spans = Vec::new();
+    for i in 0..words.len() {
+        for j in i + 1..=words.len() {
+            spans.push(words[i..j].join("_"));
+        }
+    }
+    let rows = sqlx::query_as!(
+        WordRoot,
+        r#"SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at
+           FROM standard_word_roots
+           WHERE lower(en_abbr) = ANY($1) OR lower(replace(en_full_name, ' ', '_')) = ANY($1)
+           ORDER BY (lower(en_abbr) = ANY($1)) DESC, id"#,
+        &spans
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_key: HashMap<String, Vec<WordRoot>> = HashMap::new();
+    for root in rows {
+        let abbr = root.en_abbr.to_lowercase();
+        let full = root.en_full_name.as_deref().map(|f| f.to_lowercase().replace(' ', "_"));
+        if let Some(full) = full.filter(|f| *f != abbr && spans.contains(f)) {
+            by_key.entry(full).or_default().push(root.clone());
+        }
+        if spans.contains(&abbr) {
+            by_key.entry(abbr).or_default().push(root);
+        }
+    }
+
+    let mut tokens = Vec::new();
+    let mut unmatched = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        let hit = (i + 1..=words.len()).rev().find_map(|j| by_key.get(&words[i..j].join("_")).cloned().map(|r| (j, r)));
+        match hit {
+            Some((j, roots)) => {
+                tokens.push(TokenMatch { token: words[i..j].join("_"), roots });
+                i = j;
+            }
+            None => {
+                unmatched.push(words[i].clone());
+                tokens.push(TokenMatch { token: words[i].clone(), roots: vec![] });
+                i += 1;
+            }
+        }
+    }
+
+    let suggested_cn_name = (unmatched.is_empty() && !tokens.is_empty())
+        .then(|| tokens.iter().map(|t| t.roots[0].cn_name.as_str()).collect::<String>());
+    let composition: Vec<i32> = if unmatched.is_empty() {
+        tokens.iter().map(|t| t.roots[0].id).collect()
+    } else {
+        vec![]
+    };
+
+    let exact = sqlx::query_scalar!(
+        r#"SELECT id FROM standard_fields
+           WHERE lower(field_en_name) = $1
+              OR (cardinality($2::INT[]) > 0 AND composition_ids = $2)
+           ORDER BY (lower(field_en_name) = $1) DESC, id
+           LIMIT $3"#,
+        normalized,
+        &composition,
+        REVERSE_FIELD_LIMIT
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let ranked = if exact.is_empty() {
+        search_service::recall_english(pool, name, REVERSE_FIELD_LIMIT)
+            .await?
+            .into_iter()
+            .map(|(id, score)| (id, score, ScoreBreakdown { english: Some(score), ..Default::default() }))
+            .collect()
+    } else {
+        exact
+            .into_iter()
+            .map(|id| (id, 1.0, ScoreBreakdown { exact: Some(1.0), ..Default::default() }))
+            .collect()
+    };
+    let fields = search_service::hydrate_fields(pool, ranked).await?;
+
+    Ok(ReverseMapping {
+        input: name.to_string(),
+        normalized,
+        tokens,
+        unmatched,
+        suggested_cn_name,
+        fields,
+    })
+}
@@ -1 +1,19 @@
 pub mod mapping_service;
+pub mod vector_service;
+pub mod task_service;
+pub mod search_service;
+pub mod event_service;
+pub mod webhook_service;
+pub mod mail_service;
+pub mod catalog_service;
+pub mod pinyin_service;
+pub mod autocomplete_service;
+pub mod proposal_service;
+pub mod segment_service;
+pub mod stopword_service;
+pub mod tokenizer_service;
+pub mod abbreviation_service;
+pub mod lint_service;
+pub mod synonym_service;
+pub mod label_service;
+pub mod governance_service;
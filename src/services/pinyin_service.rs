@@ -0,0 +1,191 @@
+use once_cell::sync::Lazy;
+use pinyin::ToPinyinMulti;
+use sqlx::PgPool;
+use std::collections::HashSet;
+
+/// 多音字组合的上限，超出的读音组合不再展开
+const MAX_VARIANTS: usize = 8;
+
+/// 一个中文词的 (全拼, 首字母) 读音组合，如 `长度` → (`changdu`, `cd`)、(`zhangdu`, `zd`)
+///
+/// 多音字按各读音展开，组合数不超过 [`MAX_VARIANTS`]；汉字以外的字母数字原样保留（转小写），其余字符丢弃。
+pub fn to_pinyin(text: &str) -> Vec<(String, String)> {
+    let mut variants = vec![(String::new(), String::new())];
+    for c in text.chars() {
+        if let Some(multi) = c.to_pinyin_multi() {
+            // 不同声调的同一拼写只保留一个
+            let mut readings: Vec<(&str, &str)> = Vec::new();
+            for py in (0..multi.count()).map(|i| multi.get(i)) {
+                if !readings.iter().any(|(plain, _)| *plain == py.plain()) {
+                    readings.push((py.plain(), py.first_letter()));
+                }
+            }
+            let mut next = Vec::new();
+            for (full, initials) in &variants {
+                for (plain, letter) in &readings {
+                    if next.len() < MAX_VARIANTS {
+                        next.push((format!("{}{}", full, plain), format!("{}{}", initials, letter)));
+                    }
+                }
+            }
+            variants = next;
+        } else if c.is_ascii_alphanumeric() {
+            let c = c.to_ascii_lowercase();
+            for (full, initials) in variants.iter_mut() {
+                full.push(c);
+                initials.push(c);
+            }
+        }
+    }
+    variants
+}
+
+/// 名称及其同义词的拼音索引，各词条以空格分隔并去重
+fn index_text(name: &str, terms: Option<&str>) -> (String, String) {
+    let mut full: Vec<String> = Vec::new();
+    let mut initials: Vec<String> = Vec::new();
+    let words = std::iter::once(name).chain(terms.unwrap_or("").split([' ', ',', '，']));
+    for word in words {
+        for (f, i) in to_pinyin(word.trim()) {
+            if !f.is_empty() && !full.contains(&f) {
+                full.push(f);
+            }
+            if !i.is_empty() && !initials.contains(&i) {
+                initials.push(i);
+            }
+        }
+    }
+    (full.join(" "), initials.join(" "))
+}
+
+/// 常用汉字（基本区）的全部无调拼音音节，只保留纯 ASCII 拼写（`lü` 等不计入）
+static SYLLABLES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    ('\u{4e00}'..='\u{9fff}')
+        .filter_map(|c| c.to_pinyin_multi())
+        .flat_map(|multi| (0..multi.count()).map(move |i| multi.get(i).plain()))
+        .filter(|s| s.is_ascii())
+        .collect()
+});
+
+/// 最长音节的字母数（`zhuang`、`shuang`）
+const MAX_SYLLABLE_LEN: usize = 6;
+
+/// 整段能否完整切分为拼音音节
+fn is_syllables(s: &str) -> bool {
+    // reachable[i]：前 i 个字母可以切成若干音节
+    let mut reachable = vec![false; s.len() + 1];
+    reachable[0] = true;
+    for end in 1..=s.len() {
+        let start = end.saturating_sub(MAX_SYLLABLE_LEN);
+        reachable[end] = (start..end).any(|i| reachable[i] && SYLLABLES.contains(&s[i..end]));
+    }
+    reachable[s.len()]
+}
+
+/// 首字母缩写的最大长度，更长的辅音串基本不是拼音首字母
+const MAX_INITIALS_LEN: usize = 8;
+
+/// 整段是否都是声母首字母（如 `khbh`）
+///
+/// 只接受辅音字母：带元音的 `code`、`order` 等英文单词不算；
+/// 零声母音节（`an`、`e`）的首字母是元音，不作首字母输入识别。
+fn is_initials(s: &str) -> bool {
+    s.len() <= MAX_INITIALS_LEN && s.chars().all(|c| !matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'v'))
+}
+
+/// 判断检索词是否是拼音输入，是则返回去掉空格与隔音符后的小写形式
+///
+/// 只接受 ASCII 字母、空格与 `'`（如 `jine`、`ke hu`、`xi'an`、`khbh`），
+/// 且以空格 / `'` 分隔的每一段都须能完整切分为拼音音节，或全部由首字母组成；
+/// `amount` 这类英文单词不算拼音。
+pub fn normalize_query(q: &str) -> Option<String> {
+    let q = q.trim().to_ascii_lowercase();
+    if q.is_empty() || !q.chars().all(|c| c.is_ascii_alphabetic() || c == ' ' || c == '\'') {
+        return None;
+    }
+    let mut parts = q.split([' ', '\'']).filter(|p| !p.is_empty()).peekable();
+    parts.peek()?;
+    if !parts.all(|p| is_syllables(p) || is_initials(p)) {
+        return None;
+    }
+    Some(q.chars().filter(char::is_ascii_alphabetic).collect())
+}
+
+/// 刷新词根的拼音索引；`ids` 为 None 时全量检查，只写入有变化的行
+pub async fn sync_roots(pool: &PgPool, ids: Option<&[i32]>) -> Result<u64, sqlx::Error> {
+    let rows = sqlx::query!(
+        "SELECT id, cn_name, associated_terms FROM standard_word_roots WHERE $1::INT[] IS NULL OR id = ANY($1)",
+        ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut row_ids = Vec::with_capacity(rows.len());
+    let mut fulls = Vec::with_capacity(rows.len());
+    let mut initials = Vec::with_capacity(rows.len());
+    for r in rows {
+        let (f, i) = index_text(&r.cn_name, r.associated_terms.as_deref());
+        row_ids.push(r.id);
+        fulls.push(f);
+        initials.push(i);
+    }
+
+    let res = sqlx::query!(
+        r#"UPDATE standard_word_roots r SET cn_pinyin = v.pinyin, cn_initials = v.initials
+           FROM UNNEST($1::INT[], $2::TEXT[], $3::TEXT[]) AS v(id, pinyin, initials)
+           WHERE r.id = v.id
+             AND (r.cn_pinyin IS DISTINCT FROM v.pinyin OR r.cn_initials IS DISTINCT FROM v.initials)"#,
+        &row_ids,
+        &fulls,
+        &initials
+    )
+    .execute(pool)
+    .await?;
+    Ok(res.rows_affected())
+}
+
+/// 刷新标准字段的拼音索引；`ids` 为 None 时全量检查，只写入有变化的行
+pub async fn sync_fields(pool: &PgPool, ids: Option<&[i32]>) -> Result<u64, sqlx::Error> {
+    let rows = sqlx::query!(
+        "SELECT id, field_cn_name, associated_terms FROM standard_fields WHERE $1::INT[] IS NULL OR id = ANY($1)",
+        ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut row_ids = Vec::with_capacity(rows.len());
+    let mut fulls = Vec::with_capacity(rows.len());
+    let mut initials = Vec::with_capacity(rows.len());
+    for r in rows {
+        let (f, i) = index_text(&r.field_cn_name, r.associated_terms.as_deref());
+        row_ids.push(r.id);
+        fulls.push(f);
+        initials.push(i);
+    }
+
+    let res = sqlx::query!(
+        r#"UPDATE standard_fields f SET cn_pinyin = v.pinyin, cn_initials = v.initials
+           FROM UNNEST($1::INT[], $2::TEXT[], $3::TEXT[]) AS v(id, pinyin, initials)
+           WHERE f.id = v.id
+             AND (f.cn_pinyin IS DISTINCT FROM v.pinyin OR f.cn_initials IS DISTINCT FROM v.initials)"#,
+        &row_ids,
+        &fulls,
+        &initials
+    )
+    .execute(pool)
+    .await?;
+    Ok(res.rows_affected())
+}
+
+/// 写入后刷新，失败只记录日志（启动时的全量检查会补齐）
+pub async fn refresh_roots(pool: &PgPool, ids: &[i32]) {
+    if let Err(e) = sync_roots(pool, Some(ids)).await {
+        tracing::warn!("--- 词根拼音索引刷新失败: {}", e);
+    }
+}
+
+pub async fn refresh_fields(pool: &PgPool, ids: &[i32]) {
+    if let Err(e) = sync_fields(pool, Some(ids)).await {
+        tracing::warn!("--- 字段拼音索引刷新失败: {}", e);
+    }
+}
@@ -0,0 +1,411 @@
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+use crate::AppState;
+use crate::models::field::CreateFieldRequest;
+use crate::models::word_root::WordRoot;
+use crate::services::mapping_service::{self, Candidate, Segment, SemanticOptions};
+use crate::services::pinyin_service;
+use crate::services::stopword_service::{self, IgnoredToken};
+use crate::services::segment_service::{self, Segmenter};
+
+/// 每段参与组合的候选数
+const CANDIDATES_PER_SEGMENT: usize = 3;
+/// 组合搜索时保留的部分组合数（束宽）
+const BEAM_WIDTH: usize = 50;
+
+/// 按末位词根中文名推断数据类型的规则，按顺序匹配，命中关键字的后缀即采用
+const DATA_TYPE_RULES: &[(&[&str], &str)] = &[
+    (&["日期"], "DATE"),
+    (&["时间", "时刻", "时间戳"], "TIMESTAMP"),
+    (&["金额", "价格", "单价", "费用", "余额", "价", "费", "款"], "DECIMAL(18,2)"),
+    (&["比例", "占比", "百分比", "率"], "DECIMAL(10,4)"),
+    (&["数量", "次数", "个数", "天数", "年龄", "数"], "INTEGER"),
+    (&["是否", "标志", "标识"], "BOOLEAN"),
+    (&["代码", "编码", "类型", "状态", "等级"], "VARCHAR(32)"),
+    (&["编号", "号码", "号", "ID"], "VARCHAR(64)"),
+    (&["名称", "姓名", "名"], "VARCHAR(200)"),
+    (&["描述", "说明", "备注", "地址"], "VARCHAR(500)"),
+];
+const DEFAULT_DATA_TYPE: &str = "VARCHAR(100)";
+
+/// 数据类型的推断依据
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataTypeSource {
+    ExistingFields, // 以该词根结尾的已有标准字段中最常用的类型
+    Rule,           // 末位词根中文名命中关键字规则
+    Default,        // 无法推断时的缺省类型
+}
+
+/// 一个可直接保存的字段方案
+#[derive(Serialize)]
+pub struct FieldProposal {
+    pub field: CreateFieldRequest,         // 可原样提交给 POST /fields
+    pub roots: Vec<WordRoot>,              // 组成词根，按顺序
+    pub confidence: f32,                   // 各段候选得分的几何平均
+    pub data_type_source: DataTypeSource,
+    pub data_type_rule: Option<String>,    // 命中的规则关键字
+    pub existing_field_id: Option<i32>,    // 相同组成的标准字段已存在时给出其 ID
+}
+
+#[derive(Serialize)]
+pub struct ProposalResult {
+    pub input: String,
+    pub segments: Vec<Segment>,        // 分词与各段候选（同 /suggest）
+    pub ignored: Vec<IgnoredToken>,    // 被忽略的停用词与标点
+    pub unmatched: Vec<String>,        // 没有任何候选的段，存在时无法给出完整方案
+    pub proposals: Vec<FieldProposal>, // 按置信度降序，第一个为推荐方案
+}
+
+/// 按末位词根中文名的关键字规则推断数据类型，均未命中时取缺省类型
+fn rule_data_type(last: &WordRoot) -> (String, DataTypeSource, Option<String>) {
+    for (keywords, data_type) in DATA_TYPE_RULES {
+        if let Some(keyword) = keywords.iter().find(|k| last.cn_name.ends_with(**k)) {
+            return (data_type.to_string(), DataTypeSource::Rule, Some(keyword.to_string()));
+        }
+    }
+    (DEFAULT_DATA_TYPE.to_string(), DataTypeSource::Default, None)
+}
+
+/// 以各词根结尾的已有（未废弃）标准字段中最常用的数据类型，返回 词根 ID → 类型
+async fn common_data_types(pool: &PgPool, last_ids: &[i32]) -> Result<HashMap<i32, String>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT DISTINCT ON (last_id) last_id as "last_id!", data_type as "data_type!"
+           FROM (SELECT composition_ids[cardinality(composition_ids)] as last_id, data_type
+                 FROM standard_fields
+                 WHERE composition_ids[cardinality(composition_ids)] = ANY($1)
+                   AND data_type IS NOT NULL AND data_type <> '' AND NOT COALESCE(is_deprecated, false)) t
+           GROUP BY last_id, data_type
+           ORDER BY last_id, count(*) DESC, data_type"#,
+        last_ids
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|r| (r.last_id, r.data_type)).collect())
+}
+
+/// 组成完全相同的已有标准字段，返回 组成 → 字段（数组以文本形式比较，如 `{1,2}`）
+async fn existing_compositions(
+    pool: &PgPool,
+    compositions: &[Vec<i32>],
+) -> Result<HashMap<Vec<i32>, ExistingField>, sqlx::Error> {
+    let keys: Vec<String> = compositions.iter().map(|c| composition_key(c)).collect();
+    let rows = sqlx::query!(
+        r#"SELECT DISTINCT ON (composition_ids) id, field_cn_name, field_en_name, composition_ids as "composition_ids!"
+           FROM standard_fields WHERE composition_ids::TEXT = ANY($1)
+           ORDER BY composition_ids, id"#,
+        &keys
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let field = ExistingField {
+                id: r.id,
+                field_cn_name: r.field_cn_name,
+                field_en_name: r.field_en_name,
+                matched_by: "composition",
+            };
+            (r.composition_ids, field)
+        })
+        .collect())
+}
+
+fn composition_key(ids: &[i32]) -> String {
+    format!("{{{}}}", ids.iter().map(i32::to_string).collect::<Vec<_>>().join(","))
+}
+
+/// 按词根缩写生成字段英文名，如 [order, pay, amt] → `order_pay_amt`
+pub fn build_en_name(roots: &[&WordRoot]) -> String {
+    roots.iter().map(|r| r.en_abbr.trim().to_lowercase()).collect::<Vec<_>>().join("_")
+}
+
+/// 按词根中文名生成字段中文名，如 [订单, 支付, 金额] → `订单支付金额`
+///
+/// 不取原始输入：拼音输入（`khbh`）与被忽略的停用词都不会进入字段名。
+pub fn build_cn_name(roots: &[&WordRoot]) -> String {
+    roots.iter().map(|r| r.cn_name.trim()).collect()
+}
+
+/// 在各段候选中选出置信度最高的若干组合（束搜索，得分为候选得分的几何平均）
+fn best_combinations(segments: &[Segment], limit: usize) -> Vec<(Vec<&Candidate>, f32)> {
+    let mut beam: Vec<(Vec<&Candidate>, f32)> = vec![(Vec::new(), 0.0)];
+    for segment in segments {
+        let mut next = Vec::new();
+        for (combo, log_sum) in &beam {
+            for c in segment.candidates.iter().take(CANDIDATES_PER_SEGMENT) {
+                // 同一词根在一个字段中只出现一次
+                if combo.iter().any(|p| p.root.id == c.root.id) {
+                    continue;
+                }
+                let mut combo = combo.clone();
+                combo.push(c);
+                next.push((combo, log_sum + c.score.max(f32::EPSILON).ln()));
+            }
+        }
+        next.sort_by(|a, b| b.1.total_cmp(&a.1));
+        next.truncate(BEAM_WIDTH);
+        beam = next;
+    }
+    beam.truncate(limit);
+    beam.into_iter()
+        .filter(|(combo, _)| !combo.is_empty())
+        .map(|(combo, log_sum)| {
+            let confidence = (log_sum / combo.len() as f32).exp();
+            (combo, confidence)
+        })
+        .collect()
+}
+
+/// 为多组分词结果生成字段方案；数据类型与已有组成各用一次查询
+///
+/// 返回每组的方案，以及组成相同的已有字段（按方案顺序去重）。
+async fn build_proposals(
+    pool: &PgPool,
+    items: &[&[Segment]],
+    limit: usize,
+) -> Result<Vec<(Vec<FieldProposal>, Vec<ExistingField>)>, sqlx::Error> {
+    let combos: Vec<Vec<(Vec<&Candidate>, f32)>> = items
+        .iter()
+        .map(|segments| {
+            // 任一段没有候选时无法给出完整方案
+            if segments.iter().any(|s| s.candidates.is_empty()) {
+                vec![]
+            } else {
+                best_combinations(segments, limit)
+            }
+        })
+        .collect();
+
+    let all = combos.iter().flatten();
+    let last_ids: Vec<i32> = all.clone().filter_map(|(combo, _)| combo.last().map(|c| c.root.id)).collect();
+    let compositions: Vec<Vec<i32>> = all.map(|(combo, _)| combo.iter().map(|c| c.root.id).collect()).collect();
+    let (data_types, existing) = tokio::try_join!(
+        common_data_types(pool, &last_ids),
+        existing_compositions(pool, &compositions)
+    )?;
+
+    Ok(combos
+        .into_iter()
+        .map(|combos| {
+            let mut same_composition: Vec<ExistingField> = Vec::new();
+            let proposals = combos
+                .into_iter()
+                .filter_map(|(combo, confidence)| {
+                    let last = &combo.last()?.root;
+                    let (data_type, data_type_source, data_type_rule) = match data_types.get(&last.id) {
+                        Some(t) => (t.clone(), DataTypeSource::ExistingFields, None),
+                        None => rule_data_type(last),
+                    };
+                    let roots: Vec<&WordRoot> = combo.iter().map(|c| &c.root).collect();
+                    let composition_ids: Vec<i32> = roots.iter().map(|r| r.id).collect();
+                    let existing_field = existing.get(&composition_ids);
+                    if let Some(f) = existing_field.filter(|f| !same_composition.iter().any(|e| e.id == f.id)) {
+                        same_composition.push(f.clone());
+                    }
+                    Some(FieldProposal {
+                        existing_field_id: existing_field.map(|f| f.id),
+                        field: CreateFieldRequest {
+                            field_cn_name: build_cn_name(&roots),
+                            field_en_name: build_en_name(&roots),
+                            composition_ids,
+                            data_type: Some(data_type),
+                            associated_terms: None,
+                            domain: None,
+                            ..Default::default()
+                        },
+                        roots: roots.into_iter().cloned().collect(),
+                        confidence,
+                        data_type_source,
+                        data_type_rule,
+                    })
+                })
+                .collect();
+            (proposals, same_composition)
+        })
+        .collect())
+}
+
+fn unmatched_words(segments: &[Segment]) -> Vec<String> {
+    segments.iter().filter(|s| s.candidates.is_empty()).map(|s| s.word.clone()).collect()
+}
+
+/// 由中文描述生成完整的字段方案：分词选词根 → 组合 → 英文名 → 数据类型
+pub async fn propose_fields(
+    state: &AppState,
+    input: &str,
+    segmenter: Segmenter,
+    semantic: SemanticOptions,
+    limit: usize,
+) -> Result<ProposalResult, sqlx::Error> {
+    let input = input.trim();
+    let mapping_service::Suggestion { segments, ignored } =
+        mapping_service::suggest_field_name(state, input, segmenter, semantic).await?;
+    let unmatched = unmatched_words(&segments);
+    let (proposals, _) = build_proposals(&state.db, &[&segments], limit).await?.pop().unwrap_or_default();
+
+    Ok(ProposalResult { input: input.to_string(), segments, ignored, unmatched, proposals })
+}
+
+/// 与输入对应的已有标准字段
+#[derive(Clone, Serialize)]
+pub struct ExistingField {
+    pub id: i32,
+    pub field_cn_name: String,
+    pub field_en_name: String,
+    pub matched_by: &'static str, // name / synonym / composition
+}
+
+/// 批量映射中单个输入的结果
+#[derive(Serialize)]
+pub struct BatchProposal {
+    pub input: String,
+    pub exists: bool,                       // 已有标准字段与之对应（名称、同义词或组成相同）
+    pub existing_fields: Vec<ExistingField>,
+    pub segments: Vec<Segment>,
+    pub ignored: Vec<IgnoredToken>,
+    pub unmatched: Vec<String>,
+    pub proposals: Vec<FieldProposal>,
+}
+
+#[derive(Serialize)]
+pub struct BatchResult {
+    pub total: usize,
+    pub existing: usize,   // 已有标准字段对应的输入数
+    pub proposed: usize,   // 给出了完整方案的输入数
+    pub unresolved: usize, // 既无已有字段也无完整方案的输入数
+    pub items: Vec<BatchProposal>,
+}
+
+/// 批量生成字段方案
+///
+/// 与单条建议的流程相同，但按集合查询：已有字段、整词匹配、分词后各段匹配、数据类型与已有组成
+/// 各只查询一次，与输入条数无关。语义召回只用于 SQL 匹配无结果的段，同样一次批量检索。
+pub async fn propose_fields_batch(
+    state: &AppState,
+    inputs: Vec<String>,
+    segmenter: Segmenter,
+    semantic: SemanticOptions,
+    limit: usize,
+) -> Result<BatchResult, sqlx::Error> {
+    let pool = &state.db;
+    let inputs: Vec<String> = inputs.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+    let mut distinct: Vec<String> = inputs.clone();
+    distinct.sort();
+    distinct.dedup();
+
+    // 1. 名称或同义词与输入相同的已有标准字段
+    let rows = sqlx::query!(
+        r#"SELECT w.input as "input!", f.id, f.field_cn_name, f.field_en_name, (f.field_cn_name = w.input) as "by_name!"
+           FROM unnest($1::TEXT[]) AS w(input)
+           JOIN standard_fields f
+             ON f.field_cn_name = w.input
+             OR w.input = ANY(regexp_split_to_array(COALESCE(f.associated_terms, ''), '[\s,，]+'))
+           ORDER BY f.id"#,
+        &distinct
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut existing_by_input: HashMap<String, Vec<ExistingField>> = HashMap::new();
+    for r in rows {
+        existing_by_input.entry(r.input).or_default().push(ExistingField {
+            id: r.id,
+            field_cn_name: r.field_cn_name,
+            field_en_name: r.field_en_name,
+            matched_by: if r.by_name { "name" } else { "synonym" },
+        });
+    }
+
+    // 2. 整词匹配（不做模糊），命中即不再切分
+    let whole = mapping_service::match_roots_batch(pool, &distinct, false).await?;
+
+    // 3. 其余输入切分后，所有不同的段一次性模糊匹配；词典切分的词典与拼音词条也各只加载一次
+    let (pinyin, chinese): (Vec<_>, Vec<_>) = distinct
+        .iter()
+        .filter(|i| !whole.contains_key(*i))
+        .map(|i| (i.clone(), pinyin_service::normalize_query(i)))
+        .partition(|(_, py)| py.is_some());
+    let mut words_by_input: HashMap<String, Vec<String>> = HashMap::new();
+    let mut ignored_by_input: HashMap<String, Vec<IgnoredToken>> = HashMap::new();
+
+    let pys: Vec<String> = pinyin.iter().filter_map(|(_, py)| py.clone()).collect();
+    if !pys.is_empty() {
+        let entries = mapping_service::pinyin_entries(pool, &pys).await?;
+        for (input, py) in pinyin {
+            let words = mapping_service::cut_pinyin(py.as_deref().unwrap_or_default(), &entries);
+            words_by_input.insert(input, words);
+        }
+    }
+
+    let chinese: Vec<String> = chinese.into_iter().map(|(input, _)| input).collect();
+    let dict = match segmenter {
+        Segmenter::Dict if !chinese.is_empty() => match segment_service::load_dictionary(pool, &chinese).await {
+            Ok(dict) => Some(dict),
+            Err(e) => {
+                tracing::warn!("--- 词典切分失败，改用 Jieba: {}", e);
+                None
+            }
+        },
+        _ => None,
+    };
+    for input in chinese {
+        let (words, ignored) = match &dict {
+            Some(dict) => stopword_service::filter(segment_service::segment(&input, dict).matching_words().await),
+            None => mapping_service::cut_jieba(&input).await,
+        };
+        ignored_by_input.insert(input.clone(), ignored);
+        words_by_input.insert(input, words);
+    }
+    let mut words: Vec<String> = words_by_input.values().flatten().cloned().collect();
+    words.sort();
+    words.dedup();
+    let mut by_word = mapping_service::match_roots_batch(pool, &words, true).await?;
+    mapping_service::add_semantic_matches(state, &words, &mut by_word, true, semantic).await?;
+
+    let segments: Vec<Vec<Segment>> = inputs
+        .iter()
+        .map(|input| match whole.get(input) {
+            Some(candidates) => vec![mapping_service::build_segment(input.clone(), candidates.clone())],
+            None => words_by_input
+                .get(input)
+                .map(|words| {
+                    words
+                        .iter()
+                        .map(|w| mapping_service::build_segment(w.clone(), by_word.get(w).cloned().unwrap_or_default()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    // 4. 组合、英文名与数据类型
+    let slices: Vec<&[Segment]> = segments.iter().map(Vec::as_slice).collect();
+    let proposals = build_proposals(pool, &slices, limit).await?;
+
+    let mut items = Vec::with_capacity(inputs.len());
+    for ((input, segments), (proposals, same_composition)) in inputs.iter().zip(segments).zip(proposals) {
+        let mut existing_fields = existing_by_input.get(input).cloned().unwrap_or_default();
+        for f in same_composition {
+            if !existing_fields.iter().any(|e| e.id == f.id) {
+                existing_fields.push(f);
+            }
+        }
+        items.push(BatchProposal {
+            input: input.clone(),
+            exists: !existing_fields.is_empty(),
+            existing_fields,
+            unmatched: unmatched_words(&segments),
+            segments,
+            ignored: ignored_by_input.get(input).cloned().unwrap_or_default(),
+            proposals,
+        });
+    }
+
+    let existing = items.iter().filter(|i| i.exists).count();
+    let proposed = items.iter().filter(|i| !i.proposals.is_empty()).count();
+    let unresolved = items.iter().filter(|i| !i.exists && i.proposals.is_empty()).count();
+    Ok(BatchResult { total: items.len(), existing, proposed, unresolved, items })
+}
@@ -0,0 +1,489 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, PgPool, Row};
+use std::collections::HashMap;
+use std::env;
+
+use crate::AppState;
+use crate::models::field::StandardField;
+use crate::models::word_root::WordRoot;
+use crate::services::{mapping_service, pinyin_service, vector_service};
+
+/// 列表/检索/分词匹配使用的 pg_trgm 相似度下限，可通过 `SEARCH_TRGM_THRESHOLD` 调整（0~1，默认 0.3）
+static TRGM_THRESHOLD: Lazy<f32> = Lazy::new(|| {
+    env::var("SEARCH_TRGM_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| (0.0..=1.0).contains(v))
+        .unwrap_or(0.3)
+});
+
+pub fn trgm_threshold() -> f32 {
+    *TRGM_THRESHOLD
+}
+
+/// 在当前事务内设置 pg_trgm 的相似度阈值
+///
+/// `%` / `<%` 运算符按该阈值判断是否命中，并且可以走 GIN trigram 索引；
+/// 直接比较 `similarity(..) >= $n` 只能逐行计算。
+pub async fn set_trgm_threshold(conn: &mut PgConnection, threshold: f32) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "SELECT set_config('pg_trgm.similarity_threshold', $1, true) AS similarity,
+                set_config('pg_trgm.word_similarity_threshold', $1, true) AS word_similarity",
+        threshold.to_string()
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
+
+/// 带相关度得分的结果，序列化时与原对象的字段平铺在同一层
+#[derive(Debug, Serialize)]
+pub struct Scored<T> {
+    #[serde(flatten)]
+    pub item: T,
+    pub score: Option<f32>, // 未带检索词时为 null
+}
+
+/// 动态查询中由 `score` 列承载得分，其余列交给内层类型解析
+impl<'r, T: FromRow<'r, PgRow>> FromRow<'r, PgRow> for Scored<T> {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Scored { item: T::from_row(row)?, score: row.try_get("score")? })
+    }
+}
+
+/// 字符相似度（pg_trgm similarity）达到该值即视为疑似重复
+const DUPLICATE_TRGM_THRESHOLD: f32 = 0.3;
+/// 语义相似度（余弦）达到该值即视为疑似重复
+const DUPLICATE_VECTOR_THRESHOLD: f32 = 0.75;
+
+/// 命中来源（召回通道）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchSource {
+    Exact,   // 名称全等 / 同义词命中
+    Trigram, // pg_trgm 字符相似、子串或拼音命中
+    Vector,  // Qdrant 语义相似
+    English, // 英文名 / 词根缩写按单词匹配
+}
+
+/// 各路召回的原始得分，未命中的一路为 null
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScoreBreakdown {
+    pub exact: Option<f32>,
+    pub trigram: Option<f32>,
+    pub vector: Option<f32>,
+    pub english: Option<f32>,
+}
+
+impl ScoreBreakdown {
+    fn slot(&mut self, source: MatchSource) -> &mut Option<f32> {
+        match source {
+            MatchSource::Exact => &mut self.exact,
+            MatchSource::Trigram => &mut self.trigram,
+            MatchSource::Vector => &mut self.vector,
+            MatchSource::English => &mut self.english,
+        }
+    }
+
+    /// 主要命中来源：按 精确 > 英文 > 字符 > 语义 的优先级取第一个命中的通道
+    fn primary(&self) -> MatchSource {
+        if self.exact.is_some() {
+            MatchSource::Exact
+        } else if self.english.is_some() {
+            MatchSource::English
+        } else if self.trigram.is_some() {
+            MatchSource::Trigram
+        } else {
+            MatchSource::Vector
+        }
+    }
+}
+
+/// 标准字段检索结果：SQL 与向量命中统一回表，返回完整字段及其词根组成
+#[derive(Debug, Serialize)]
+pub struct FieldMatch {
+    pub field: StandardField,
+    pub roots: Vec<WordRoot>,   // 按 composition_ids 顺序展开的词根
+    pub source: MatchSource,
+    pub score: f32,
+    pub scores: ScoreBreakdown, // 各路得分明细
+}
+
+/// 词根检索结果
+#[derive(Debug, Serialize)]
+pub struct RootMatch {
+    pub root: WordRoot,
+    pub source: MatchSource,
+    pub score: f32,
+    pub scores: ScoreBreakdown,
+}
+
+/// 按 ID 批量读取标准字段（顺序不保证）
+async fn load_fields(pool: &PgPool, ids: &[i32]) -> Result<Vec<StandardField>, sqlx::Error> {
+    sqlx::query_as!(
+        StandardField,
+        r#"SELECT id, field_cn_name, field_en_name, composition_ids as "composition_ids!",
+                  data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at
+           FROM standard_fields WHERE id = ANY($1)"#,
+        ids
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 按 ID 批量读取词根（顺序不保证）
+pub async fn load_roots(pool: &PgPool, ids: &[i32]) -> Result<HashMap<i32, WordRoot>, sqlx::Error> {
+    let roots = sqlx::query_as!(
+        WordRoot,
+        "SELECT id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at
+         FROM standard_word_roots WHERE id = ANY($1)",
+        ids
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(roots.into_iter().map(|r| (r.id, r)).collect())
+}
+
+/// 将排好序的 (字段 ID, 得分, 明细) 回表为完整结果，保持输入顺序
+///
+/// 向量库中可能残留已删除的字段，回表时自然过滤。
+pub async fn hydrate_fields(
+    pool: &PgPool,
+    ranked: Vec<(i32, f32, ScoreBreakdown)>,
+) -> Result<Vec<FieldMatch>, sqlx::Error> {
+    let ids: Vec<i32> = ranked.iter().map(|(id, _, _)| *id).collect();
+    let mut fields: HashMap<i32, StandardField> =
+        load_fields(pool, &ids).await?.into_iter().map(|f| (f.id, f)).collect();
+
+    let mut root_ids: Vec<i32> = fields.values().flat_map(|f| f.composition_ids.iter().copied()).collect();
+    root_ids.sort_unstable();
+    root_ids.dedup();
+    let roots = load_roots(pool, &root_ids).await?;
+
+    Ok(ranked
+        .into_iter()
+        .filter_map(|(id, score, scores)| {
+            let field = fields.remove(&id)?;
+            let composed = field.composition_ids.iter().filter_map(|rid| roots.get(rid).cloned()).collect();
+            Some(FieldMatch { field, roots: composed, source: scores.primary(), score, scores })
+        })
+        .collect())
+}
+
+/// 为新字段申请查找已存在的相近标准字段
+///
+/// 同时使用 SQL 模糊匹配（中文名/同义词）与 Qdrant `standard_fields` 语义检索，
+/// 按字段 ID 去重后保留最高分，按分数降序返回。
+pub async fn find_similar_fields(state: &AppState, cn_name: &str, limit: usize) -> Vec<FieldMatch> {
+    let input = cn_name.trim();
+    if input.is_empty() {
+        return vec![];
+    }
+
+    let mut best: HashMap<i32, (f32, ScoreBreakdown)> = HashMap::new();
+
+    // 1. SQL：子串命中或 trigram 相似度超过阈值（`%` 按事务内阈值判断，可走 trigram 索引）
+    let pattern = format!("%{}%", input);
+    let sql_hits = async {
+        let mut tx = state.db.begin().await?;
+        set_trgm_threshold(&mut tx, DUPLICATE_TRGM_THRESHOLD).await?;
+        let rows = sqlx::query!(
+            r#"SELECT id, GREATEST(similarity(field_cn_name, $1),
+                                   CASE WHEN field_cn_name ILIKE $2 OR associated_terms ILIKE $2 THEN 0.5 ELSE 0 END)::REAL as "score!",
+                      (field_cn_name = $1) as "exact!"
+               FROM standard_fields
+               WHERE field_cn_name ILIKE $2 OR associated_terms ILIKE $2 OR field_cn_name % $1
+               ORDER BY 2 DESC
+               LIMIT 10"#,
+            input,
+            pattern
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(rows)
+    }
+    .await
+    .unwrap_or_default();
+
+    for hit in sql_hits {
+        let entry = best.entry(hit.id).or_default();
+        if hit.exact {
+            entry.0 = 1.0;
+            entry.1.exact = Some(1.0);
+        } else {
+            entry.0 = hit.score;
+            entry.1.trigram = Some(hit.score);
+        }
+    }
+
+    // 2. 向量：语义相近但字面不同的同义表达
+    match vector_service::search(state, "standard_fields", input, 5).await {
+        Ok(hits) => {
+            for (id, score) in hits.into_iter().filter(|(_, s)| *s >= DUPLICATE_VECTOR_THRESHOLD) {
+                let entry = best.entry(id).or_default();
+                entry.0 = entry.0.max(score);
+                entry.1.vector = Some(score);
+            }
+        }
+        Err(e) => tracing::warn!("--- 重复检测跳过语义检索: {}", e),
+    }
+
+    let mut ranked: Vec<(i32, f32, ScoreBreakdown)> =
+        best.into_iter().map(|(id, (score, scores))| (id, score, scores)).collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(limit);
+
+    hydrate_fields(&state.db, ranked).await.unwrap_or_else(|e| {
+        tracing::warn!("--- 重复检测回表失败: {}", e);
+        vec![]
+    })
+}
+
+// ---------------------------------------------------------------------------
+// 混合检索：精确/同义词 + pg_trgm + 英文 + 向量，融合排序
+// ---------------------------------------------------------------------------
+
+/// 每一路召回的候选数量
+const RECALL_LIMIT: i64 = 50;
+/// 语义召回下限，过低的余弦相似度基本是噪声
+const HYBRID_VECTOR_THRESHOLD: f32 = 0.5;
+/// RRF 平滑常数：score = Σ 1 / (k + rank)
+const RRF_K: f32 = 60.0;
+/// 加权融合的权重（精确 / 字符 / 语义），和为 1；英文通道只在输入含英文单词时参与
+const WEIGHT_EXACT: f32 = 0.4;
+const WEIGHT_TRIGRAM: f32 = 0.3;
+const WEIGHT_VECTOR: f32 = 0.3;
+const WEIGHT_ENGLISH: f32 = 0.3;
+
+/// 多路结果的融合方式
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FusionMode {
+    /// 倒数排名融合，只看各路名次，不受各路分数量纲影响
+    #[default]
+    Rrf,
+    /// 按各路原始分数加权求和
+    Weighted,
+}
+
+fn weight(source: MatchSource) -> f32 {
+    match source {
+        MatchSource::Exact => WEIGHT_EXACT,
+        MatchSource::Trigram => WEIGHT_TRIGRAM,
+        MatchSource::Vector => WEIGHT_VECTOR,
+        MatchSource::English => WEIGHT_ENGLISH,
+    }
+}
+
+/// 精确召回：中文名 / 英文名 / 多语言名称全等（英文名按 snake_case 归一后比较），或命中某个同义词
+async fn recall_exact(pool: &PgPool, q: &str) -> Result<Vec<(i32, f32)>, sqlx::Error> {
+    let snake = mapping_service::split_identifier(q).join("_");
+    let rows = sqlx::query!(
+        r#"SELECT id, (CASE WHEN field_cn_name = $1 OR lower(field_en_name) IN (lower($1), $3) THEN 1.0 ELSE 0.9 END)::REAL as "score!"
+           FROM standard_fields
+           WHERE field_cn_name = $1 OR lower(field_en_name) IN (lower($1), $3)
+              OR $1 = ANY(regexp_split_to_array(COALESCE(associated_terms, ''), '[\s,，]+'))
+              OR id IN (SELECT entity_id FROM localized_labels WHERE entity_type = 'field' AND label = $1)
+           ORDER BY 2 DESC, id
+           LIMIT $2"#,
+        q,
+        RECALL_LIMIT,
+        snake
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|r| (r.id, r.score)).collect())
+}
+
+/// 字符召回：pg_trgm 相似度（整体相似 / 词内相似），子串命中也一并纳入；
+/// 拼音输入同时比对全拼与首字母索引，多语言名称与业务定义参与相似度与子串匹配
+///
+/// 先用 `%` / `<%` / ILIKE 在各列的 trigram 索引上取候选（多语言名称在 `label_hits` 中单独筛选），
+/// 只对候选计算得分，不逐行扫描全表。
+async fn recall_trigram(pool: &PgPool, q: &str) -> Result<Vec<(i32, f32)>, sqlx::Error> {
+    let pattern = format!("%{}%", q);
+    let py = pinyin_service::normalize_query(q);
+    let py_pattern = py.as_ref().map(|py| format!("%{}%", py));
+    let py_prefix = py.as_ref().map(|py| format!("% {}%", py));
+    let mut tx = pool.begin().await?;
+    set_trgm_threshold(&mut tx, trgm_threshold()).await?;
+    let rows = sqlx::query!(
+        r#"WITH label_hits AS (
+               SELECT entity_id, max(word_similarity($1, label)) AS score
+               FROM localized_labels
+               WHERE entity_type = 'field' AND ($1 <% label OR label ILIKE $2)
+               GROUP BY entity_id),
+           candidates AS (
+               SELECT id FROM standard_fields
+               WHERE field_cn_name % $1 OR $1 <% field_cn_name OR $1 <% associated_terms OR $1 <% business_definition
+                  OR field_cn_name ILIKE $2 OR associated_terms ILIKE $2 OR business_definition ILIKE $2
+                  OR $4 <% cn_pinyin OR $4 <% cn_initials
+                  OR (cn_pinyin LIKE $5 AND ' ' || cn_pinyin LIKE $6)
+                  OR (cn_initials LIKE $5 AND ' ' || cn_initials LIKE $6)
+               UNION
+               SELECT entity_id FROM label_hits)
+           SELECT f.id, GREATEST(similarity(f.field_cn_name, $1), word_similarity($1, f.field_cn_name),
+                                 COALESCE(word_similarity($1, f.associated_terms), 0),
+                                 COALESCE(word_similarity($1, f.business_definition), 0),
+                                 COALESCE(word_similarity($4, f.cn_pinyin), 0),
+                                 COALESCE(word_similarity($4, f.cn_initials), 0),
+                                 COALESCE(lh.score, 0))::REAL as "score!"
+           FROM candidates c
+           JOIN standard_fields f ON f.id = c.id
+           LEFT JOIN label_hits lh ON lh.entity_id = f.id
+           ORDER BY 2 DESC, f.id
+           LIMIT $3"#,
+        q,
+        pattern,
+        RECALL_LIMIT,
+        py,
+        py_pattern,
+        py_prefix
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(rows.into_iter().map(|r| (r.id, r.score)).collect())
+}
+
+/// 英文召回：将输入拆成英文单词（支持 snake_case / camelCase），
+/// 按单词在字段英文名中的覆盖率、通过词根缩写/英文全称映射到字段组成的覆盖率，
+/// 以及与英文名整体的 trigram 相似度打分，取最高者。
+///
+/// 三路命中分别由英文名 trigram 索引（ILIKE / `%`）与组成词根的 GIN 索引取得，再按字段聚合，不逐行扫描全表。
+pub async fn recall_english(pool: &PgPool, q: &str, limit: i64) -> Result<Vec<(i32, f32)>, sqlx::Error> {
+    let tokens = mapping_service::split_identifier(q);
+    if tokens.is_empty() {
+        return Ok(vec![]);
+    }
+    let snake = tokens.join("_");
+    let threshold = trgm_threshold();
+    let mut tx = pool.begin().await?;
+    set_trgm_threshold(&mut tx, threshold).await?;
+    let rows = sqlx::query!(
+        r#"WITH tok AS (SELECT DISTINCT unnest($1::TEXT[]) AS t),
+                n AS (SELECT count(*)::REAL AS n FROM tok),
+                root_tok AS (
+                    SELECT r.id, tok.t FROM standard_word_roots r
+                    JOIN tok ON lower(r.en_abbr) = tok.t
+                             OR lower(r.en_full_name) = tok.t
+                             OR lower(replace(r.en_full_name, ' ', '_')) = tok.t),
+                word_hits AS (
+                    SELECT f.id, count(DISTINCT tok.t) AS c FROM tok
+                    JOIN standard_fields f ON f.field_en_name ILIKE '%' || tok.t || '%'
+                     AND tok.t = ANY(regexp_split_to_array(lower(f.field_en_name), '_'))
+                    GROUP BY f.id),
+                comp_hits AS (
+                    SELECT f.id, count(DISTINCT rt.t) AS c FROM root_tok rt
+                    JOIN standard_fields f ON f.composition_ids @> ARRAY[rt.id]
+                    GROUP BY f.id),
+                trgm_hits AS (
+                    SELECT f.id, similarity(f.field_en_name, $2) AS s FROM standard_fields f
+                    WHERE f.field_en_name % $2)
+           SELECT id as "id!", score as "score!" FROM (
+               SELECT ids.id, GREATEST(COALESCE(w.c, 0) / n.n, COALESCE(c.c, 0) / n.n, COALESCE(t.s, 0))::REAL as score
+               FROM (SELECT id FROM word_hits UNION SELECT id FROM comp_hits UNION SELECT id FROM trgm_hits) ids
+               CROSS JOIN n
+               LEFT JOIN word_hits w ON w.id = ids.id
+               LEFT JOIN comp_hits c ON c.id = ids.id
+               LEFT JOIN trgm_hits t ON t.id = ids.id
+           ) s
+           WHERE score >= $3
+           ORDER BY score DESC, id
+           LIMIT $4"#,
+        &tokens,
+        snake,
+        threshold,
+        limit
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(rows.into_iter().map(|r| (r.id, r.score)).collect())
+}
+
+/// 混合检索标准字段
+///
+/// 精确匹配、pg_trgm、英文单词与 Qdrant 语义检索并行执行，按字段 ID 去重合并后融合排序，
+/// 返回完整的标准字段、词根组成与各路得分。任一路失败只记录日志，不影响其余结果。
+pub async fn hybrid_search_fields(
+    state: &AppState,
+    q: &str,
+    mode: FusionMode,
+    limit: usize,
+) -> Result<Vec<FieldMatch>, sqlx::Error> {
+    let q = q.trim();
+    if q.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let (exact, trigram, english, vector) = tokio::join!(
+        recall_exact(&state.db, q),
+        recall_trigram(&state.db, q),
+        recall_english(&state.db, q, RECALL_LIMIT),
+        vector_service::search(state, "standard_fields", q, RECALL_LIMIT as u64),
+    );
+    let exact = exact.unwrap_or_else(|e| {
+        tracing::warn!("--- 混合检索: 精确召回失败: {}", e);
+        vec![]
+    });
+    let trigram = trigram.unwrap_or_else(|e| {
+        tracing::warn!("--- 混合检索: 字符召回失败: {}", e);
+        vec![]
+    });
+    let english = english.unwrap_or_else(|e| {
+        tracing::warn!("--- 混合检索: 英文召回失败: {}", e);
+        vec![]
+    });
+    let vector: Vec<(i32, f32)> = match vector {
+        Ok(hits) => hits.into_iter().filter(|(_, s)| *s >= HYBRID_VECTOR_THRESHOLD).collect(),
+        Err(e) => {
+            tracing::warn!("--- 混合检索: 语义召回失败: {}", e);
+            vec![]
+        }
+    };
+
+    let mut fused: HashMap<i32, (f32, ScoreBreakdown)> = HashMap::new();
+    let channels = [
+        (MatchSource::Exact, &exact),
+        (MatchSource::Trigram, &trigram),
+        (MatchSource::English, &english),
+        (MatchSource::Vector, &vector),
+    ];
+    for (source, hits) in channels {
+        for (rank, (id, score)) in hits.iter().enumerate() {
+            let entry = fused.entry(*id).or_default();
+            *entry.1.slot(source) = Some(*score);
+            entry.0 += match mode {
+                FusionMode::Rrf => 1.0 / (RRF_K + rank as f32 + 1.0),
+                FusionMode::Weighted => weight(source) * score,
+            };
+        }
+    }
+
+    let mut ranked: Vec<(i32, f32, ScoreBreakdown)> =
+        fused.into_iter().map(|(id, (score, scores))| (id, score, scores)).collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(limit);
+
+    hydrate_fields(&state.db, ranked).await
+}
+
+/// 语义检索相近词根（Qdrant `word_roots`），命中后回表返回完整词根
+pub async fn similar_roots(state: &AppState, q: &str, limit: u64) -> Result<Vec<RootMatch>, String> {
+    let hits = vector_service::search(state, "word_roots", q, limit).await?;
+    let ids: Vec<i32> = hits.iter().map(|(id, _)| *id).collect();
+    let mut roots = load_roots(&state.db, &ids).await.map_err(|e| e.to_string())?;
+
+    Ok(hits
+        .into_iter()
+        .filter_map(|(id, score)| {
+            let root = roots.remove(&id)?;
+            let scores = ScoreBreakdown { vector: Some(score), ..Default::default() };
+            Some(RootMatch { root, source: MatchSource::Vector, score, scores })
+        })
+        .collect())
+}
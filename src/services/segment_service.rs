@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+
+/// 切分方式，缺省保持原有的 Jieba 切分，词典切分需显式指定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Segmenter {
+    Dict,  // 基于词根中文名与同义词的最大覆盖切分
+    #[default]
+    Jieba, // Jieba 精准模式
+}
+
+/// 切分结果中的一段
+#[derive(Debug, Clone, Serialize)]
+pub struct Span {
+    pub text: String,
+    pub start: usize,       // 字符下标（含）
+    pub end: usize,         // 字符下标（不含）
+    pub covered: bool,      // 是否为词典中的词（词根中文名、同义词或自定义词条）
+    pub root_ids: Vec<i32>, // 以该词为中文名或同义词的词根，自定义词条为空
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Segmentation {
+    pub spans: Vec<Span>,
+    pub coverage: f32,          // 被词典词覆盖的字符占比
+    pub uncovered: Vec<String>, // 未被覆盖的片段
+}
+
+impl Segmentation {
+    fn from_spans(spans: Vec<Span>, total_chars: usize) -> Self {
+        let covered: usize = spans.iter().filter(|s| s.covered).map(|s| s.end - s.start).sum();
+        let uncovered = spans.iter().filter(|s| !s.covered).map(|s| s.text.clone()).collect();
+        let coverage = if total_chars == 0 { 0.0 } else { covered as f32 / total_chars as f32 };
+        Self { spans, coverage, uncovered }
+    }
+
+    /// 各段文本
+    pub fn words(&self) -> Vec<String> {
+        self.spans.iter().map(|s| s.text.clone()).collect()
+    }
+
+    /// 用于词根匹配的各段：词典词原样保留，未覆盖片段再用 Jieba 细分，便于逐段模糊匹配错别字
+    pub async fn matching_words(&self) -> Vec<String> {
+        let jieba_read = crate::JIEBA.read().await;
+        let mut words = Vec::with_capacity(self.spans.len());
+        for span in &self.spans {
+            if span.covered {
+                words.push(span.text.clone());
+            } else {
+                words.extend(jieba_read.cut(&span.text, false).into_iter().map(str::to_string));
+            }
+        }
+        words
+    }
+}
+
+/// 切分词典：词 → 词根 ID，只包含在输入中出现过的词
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    terms: HashMap<String, Vec<i32>>,
+    never_split: HashSet<String>, // 不可被切开的自定义词条
+    max_len: usize,               // 最长词的字符数
+}
+
+impl Dictionary {
+    fn insert(&mut self, term: &str, root_id: Option<i32>) {
+        let ids = self.terms.entry(term.to_string()).or_default();
+        if let Some(id) = root_id.filter(|id| !ids.contains(id)) {
+            ids.push(id);
+        }
+        self.max_len = self.max_len.max(term.chars().count());
+    }
+
+    /// 每个位置能否作为分段边界：落在某个“不拆分”词条内部的位置不能
+    fn boundaries(&self, chars: &[char]) -> Vec<bool> {
+        let mut allowed = vec![true; chars.len() + 1];
+        for word in &self.never_split {
+            let w: Vec<char> = word.chars().collect();
+            for start in 0..chars.len().saturating_sub(w.len() - 1) {
+                if chars[start..start + w.len()] == w[..] {
+                    allowed[start + 1..start + w.len()].iter_mut().for_each(|a| *a = false);
+                }
+            }
+        }
+        allowed
+    }
+
+    fn get(&self, term: &str) -> Option<&Vec<i32>> {
+        self.terms.get(term)
+    }
+}
+
+/// 加载出现在任一输入中的词根中文名、同义词与自定义词条（一次查询）
+///
+/// 自定义词典中 `force_split` 的词条不作为词使用，即使它是某个词根的中文名或同义词。
+pub async fn load_dictionary(pool: &PgPool, inputs: &[String]) -> Result<Dictionary, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT DISTINCT t.term as "term!", t.root_id, t.never_split as "never_split!"
+           FROM (SELECT term, r.id as root_id, false as never_split
+                 FROM standard_word_roots r,
+                   unnest(array_prepend(r.cn_name, regexp_split_to_array(COALESCE(r.associated_terms, ''), '[\s,，]+'))) AS term
+                 UNION ALL
+                 SELECT word, NULL, rule = 'never_split' FROM segment_dictionary WHERE rule <> 'force_split') t
+           WHERE t.term <> ''
+             AND NOT EXISTS (SELECT 1 FROM segment_dictionary d WHERE d.rule = 'force_split' AND d.word = t.term)
+             AND EXISTS (SELECT 1 FROM unnest($1::TEXT[]) AS i(input) WHERE strpos(i.input, t.term) > 0)"#,
+        inputs
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut dict = Dictionary::default();
+    for r in rows {
+        dict.insert(&r.term, r.root_id);
+        if r.never_split {
+            dict.never_split.insert(r.term);
+        }
+    }
+    Ok(dict)
+}
+
+/// DP 状态的优劣：覆盖字符越多越好，其次段数越少越好
+type Score = (usize, std::cmp::Reverse<usize>);
+
+/// 在所有切分方式中选出词典覆盖字符最多、段数最少的一种
+///
+/// `best[i][g]` 表示前 i 个字符的最优切分，`g` 标记最后一段是否为未覆盖片段
+/// （相邻的未覆盖片段合并为一段，因此不允许两个未覆盖片段相接）。
+/// 分段不会在“不拆分”词条内部断开；多个此类词条交叠、遵守约束反而覆盖更少时忽略该约束。
+pub fn segment(input: &str, dict: &Dictionary) -> Segmentation {
+    let chars: Vec<char> = input.chars().collect();
+    let free = segment_with(&chars, dict, &vec![true; chars.len() + 1]).expect("不受边界约束时总有可行切分");
+    match segment_with(&chars, dict, &dict.boundaries(&chars)) {
+        Some(kept) if kept.coverage >= free.coverage => kept,
+        _ => free,
+    }
+}
+
+fn segment_with(chars: &[char], dict: &Dictionary, allowed: &[bool]) -> Option<Segmentation> {
+    let n = chars.len();
+    // (得分, 上一状态的位置, 上一状态的 g)
+    let mut best: Vec<[Option<(Score, usize, bool)>; 2]> = vec![[None, None]; n + 1];
+    best[0][0] = Some(((0, std::cmp::Reverse(0)), 0, false));
+
+    let better = |slot: &Option<(Score, usize, bool)>, score: Score| slot.is_none_or(|(s, _, _)| score > s);
+
+    for i in 0..n {
+        for g in [false, true] {
+            let Some(((covered, std::cmp::Reverse(pieces)), _, _)) = best[i][g as usize] else { continue };
+            // 词典词，长词优先尝试，同分时保留先到的切分
+            for len in (1..=dict.max_len.min(n - i)).rev() {
+                let word: String = chars[i..i + len].iter().collect();
+                if allowed[i + len] && dict.get(&word).is_some() {
+                    let score = (covered + len, std::cmp::Reverse(pieces + 1));
+                    if better(&best[i + len][0], score) {
+                        best[i + len][0] = Some((score, i, g));
+                    }
+                }
+            }
+            // 未覆盖片段
+            if !g {
+                let score = (covered, std::cmp::Reverse(pieces + 1));
+                for (slot, _) in best[i + 1..].iter_mut().zip(&allowed[i + 1..]).filter(|(_, a)| **a) {
+                    if better(&slot[1], score) {
+                        slot[1] = Some((score, i, g));
+                    }
+                }
+            }
+        }
+    }
+
+    if n > 0 && best[n][0].is_none() && best[n][1].is_none() {
+        return None;
+    }
+
+    // 回溯
+    let mut spans = Vec::new();
+    let mut g = match (best[n][0], best[n][1]) {
+        (Some(a), Some(b)) => b.0 > a.0,
+        (None, Some(_)) => true,
+        _ => false,
+    };
+    let mut end = n;
+    while end > 0 {
+        let Some((_, start, prev_g)) = best[end][g as usize] else { break };
+        let text: String = chars[start..end].iter().collect();
+        let root_ids = if g { vec![] } else { dict.get(&text).cloned().unwrap_or_default() };
+        spans.push(Span { text, start, end, covered: !g, root_ids });
+        end = start;
+        g = prev_g;
+    }
+    spans.reverse();
+    Some(Segmentation::from_spans(spans, n))
+}
+
+/// 按词典标注 Jieba 的切分结果，便于与词典切分对比
+pub async fn jieba_segment(input: &str, dict: &Dictionary) -> Segmentation {
+    let words: Vec<String> = {
+        let jieba_read = crate::JIEBA.read().await;
+        jieba_read.cut(input, false).into_iter().map(str::to_string).collect()
+    };
+    let mut spans = Vec::with_capacity(words.len());
+    let mut start = 0;
+    for text in words {
+        let end = start + text.chars().count();
+        // 自定义词条没有关联词根，但同样算作词典中的词
+        let entry = dict.get(&text);
+        let covered = entry.is_some();
+        let root_ids = entry.cloned().unwrap_or_default();
+        spans.push(Span { covered, text, start, end, root_ids });
+        start = end;
+    }
+    Segmentation::from_spans(spans, input.chars().count())
+}
+
+/// 词典切分与 Jieba 切分的对比
+#[derive(Serialize)]
+pub struct SegmentComparison {
+    pub input: String,
+    pub dict: Segmentation,
+    pub jieba: Segmentation,
+    pub same: bool, // 两种切分的分段是否完全一致
+}
+
+pub async fn compare(pool: &PgPool, input: &str) -> Result<SegmentComparison, sqlx::Error> {
+    let dict = load_dictionary(pool, &[input.to_string()]).await?;
+    let by_dict = segment(input, &dict);
+    let by_jieba = jieba_segment(input, &dict).await;
+    let same = by_dict.words() == by_jieba.words();
+    Ok(SegmentComparison { input: input.to_string(), dict: by_dict, jieba: by_jieba, same })
+}
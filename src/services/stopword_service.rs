@@ -0,0 +1,92 @@
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::AppState;
+use crate::services::event_service::DomainEvent;
+
+/// 停用词缓存，启动时加载，管理员增删改后立即重载，其他副本经 `stop_words_changed` 事件重载
+static STOP_WORDS: Lazy<RwLock<HashSet<String>>> = Lazy::new(|| RwLock::new(HashSet::new()));
+
+/// 从数据库重新加载停用词，返回词数
+pub async fn reload(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let words = sqlx::query_scalar!("SELECT word FROM segment_stop_words").fetch_all(pool).await?;
+    let count = words.len();
+    *STOP_WORDS.write() = words.into_iter().collect();
+    Ok(count)
+}
+
+/// 写入后重载，失败只记录日志（沿用旧缓存）
+pub async fn refresh(pool: &PgPool) {
+    if let Err(e) = reload(pool).await {
+        tracing::warn!("--- 停用词重载失败: {}", e);
+    }
+}
+
+/// 订阅停用词变更事件并重载缓存
+///
+/// 开启 `EVENTS_PG_NOTIFY` 时其他副本的增删改同样经事件总线到达；事件积压丢失时同样重载。
+pub fn spawn_listener(state: &AppState) {
+    let pool = state.db.clone();
+    let mut rx = state.events.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(DomainEvent::StopWordsChanged { .. }) | Err(RecvError::Lagged(_)) => refresh(&pool).await,
+                Ok(_) => {}
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+/// 分段被忽略的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IgnoreRule {
+    Punctuation, // 只含标点、空白或符号
+    StopWord,    // 与某个停用词相同
+    Particle,    // 由多个单字停用词组成（如“及其”）
+}
+
+/// 切分后被忽略的分段
+#[derive(Debug, Clone, Serialize)]
+pub struct IgnoredToken {
+    pub token: String,
+    pub rule: IgnoreRule,
+}
+
+fn classify(token: &str, stop_words: &HashSet<String>) -> Option<IgnoreRule> {
+    if !token.chars().any(char::is_alphanumeric) {
+        return Some(IgnoreRule::Punctuation);
+    }
+    if stop_words.contains(token) {
+        return Some(IgnoreRule::StopWord);
+    }
+    let mut buf = [0u8; 4];
+    if token.chars().all(|c| stop_words.contains(&*c.encode_utf8(&mut buf))) {
+        return Some(IgnoreRule::Particle);
+    }
+    None
+}
+
+/// 去掉停用词、虚词组合与标点，返回 (保留的分段, 被忽略的分段)，均保持原顺序
+pub fn filter(words: Vec<String>) -> (Vec<String>, Vec<IgnoredToken>) {
+    let stop_words = STOP_WORDS.read();
+    let mut kept = Vec::with_capacity(words.len());
+    let mut ignored = Vec::new();
+    for word in words {
+        let token = word.trim();
+        if token.is_empty() {
+            continue;
+        }
+        match classify(token, &stop_words) {
+            Some(rule) => ignored.push(IgnoredToken { token: token.to_string(), rule }),
+            None => kept.push(token.to_string()),
+        }
+    }
+    (kept, ignored)
+}
@@ -0,0 +1,230 @@
+use axum::http::StatusCode;
+use sqlx::PgConnection;
+use sqlx::PgPool;
+
+use crate::models::synonym::{ConflictSource, SynonymClaim, SynonymConflict};
+
+/// root_synonyms 上 (namespace, term) 的唯一约束
+const UNIQUE_CONSTRAINT: &str = "root_synonyms_namespace_term_key";
+
+/// 同义词命名空间：词根的业务域，未设置时为 ''
+pub fn namespace(domain: Option<&str>) -> String {
+    domain.map(str::trim).unwrap_or("").to_string()
+}
+
+/// 拆分已规范化的同义词字符串，去重并保持顺序
+pub fn split_terms(terms: Option<&str>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for t in terms.unwrap_or("").split_whitespace() {
+        if !out.iter().any(|o| o == t) {
+            out.push(t.to_string());
+        }
+    }
+    out
+}
+
+/// 剔除冲突项后的同义词字符串
+pub fn accepted_terms(terms: &[String], conflicts: &[SynonymConflict]) -> Option<String> {
+    let kept: Vec<&str> = terms
+        .iter()
+        .filter(|t| !conflicts.iter().any(|c| &c.term == *t))
+        .map(String::as_str)
+        .collect();
+    (!kept.is_empty()).then(|| kept.join(" "))
+}
+
+/// 查找与同一命名空间内其他词根冲突的同义词（已归属其他词根，或等于其他词根的中文名）
+pub async fn find_conflicts(
+    conn: &mut PgConnection,
+    root_id: Option<i32>,
+    namespace: &str,
+    terms: &[String],
+) -> Result<Vec<SynonymConflict>, sqlx::Error> {
+    if terms.is_empty() {
+        return Ok(vec![]);
+    }
+    let rows = sqlx::query!(
+        r#"SELECT t.term as "term!", r.id as "id!", r.cn_name as "cn_name!", r.en_abbr as "en_abbr!", false as "by_name!"
+           FROM unnest($1::TEXT[]) AS t(term)
+           JOIN root_synonyms s ON s.namespace = $2 AND s.term = t.term
+           JOIN standard_word_roots r ON r.id = s.root_id
+           WHERE s.root_id IS DISTINCT FROM $3
+           UNION ALL
+           SELECT t.term, r.id, r.cn_name, r.en_abbr, true
+           FROM unnest($1::TEXT[]) AS t(term)
+           JOIN standard_word_roots r ON r.cn_name = t.term AND COALESCE(r.domain, '') = $2
+           WHERE r.id IS DISTINCT FROM $3
+           ORDER BY 1, 2"#,
+        terms,
+        namespace,
+        root_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| SynonymConflict {
+            term: r.term,
+            namespace: namespace.to_string(),
+            root_id: r.id,
+            cn_name: r.cn_name,
+            en_abbr: r.en_abbr,
+            source: if r.by_name { ConflictSource::CnName } else { ConflictSource::Synonym },
+        })
+        .collect())
+}
+
+/// 查找与词根中文名相同、已归属同一命名空间内其他词根的同义词
+pub async fn find_name_conflicts(
+    conn: &mut PgConnection,
+    root_id: Option<i32>,
+    namespace: &str,
+    cn_name: &str,
+) -> Result<Vec<SynonymConflict>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT s.term, r.id, r.cn_name, r.en_abbr
+           FROM root_synonyms s
+           JOIN standard_word_roots r ON r.id = s.root_id
+           WHERE s.namespace = $1 AND s.term = $2 AND s.root_id IS DISTINCT FROM $3"#,
+        namespace,
+        cn_name.trim(),
+        root_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| SynonymConflict {
+            term: r.term,
+            namespace: namespace.to_string(),
+            root_id: r.id,
+            cn_name: r.cn_name,
+            en_abbr: r.en_abbr,
+            source: ConflictSource::Synonym,
+        })
+        .collect())
+}
+
+/// 是否因同义词在检查之后被并发写入占用而违反唯一约束
+pub fn is_claimed(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .is_some_and(|d| d.code().as_deref() == Some("23505") && d.constraint() == Some(UNIQUE_CONSTRAINT))
+}
+
+/// 并发占用时返回给调用方的说明
+pub const CLAIMED_MESSAGE: &str = "同义词已被其他词根并发占用，请刷新后重试";
+
+/// 以 terms 覆盖词根的同义词归属
+///
+/// 不跳过冲突：检查之后被并发写入占用的同义词会触发唯一约束（见 [`is_claimed`]），
+/// 调用方应回滚事务，避免 associated_terms 与归属表不一致。
+pub async fn assign(
+    conn: &mut PgConnection,
+    root_id: i32,
+    namespace: &str,
+    terms: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM root_synonyms WHERE root_id = $1", root_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query!(
+        "INSERT INTO root_synonyms (root_id, term, namespace)
+         SELECT $1, t, $2 FROM unnest($3::TEXT[]) AS t",
+        root_id,
+        namespace,
+        terms
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// 列出待处理的同义词（历史数据迁移遗留或绕过接口直接改库产生）
+pub async fn list_claims(pool: &PgPool) -> Result<Vec<SynonymClaim>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT t.term as "term!", COALESCE(r.domain, '') as "namespace!",
+                  s.root_id as "owner_root_id?", o.cn_name as "owner_cn_name?",
+                  array_agg(r.id ORDER BY r.id) as "claimant_root_ids!"
+           FROM standard_word_roots r
+           CROSS JOIN unnest(regexp_split_to_array(COALESCE(r.associated_terms, ''), '[\s,，]+')) AS t(term)
+           LEFT JOIN root_synonyms s ON s.namespace = COALESCE(r.domain, '') AND s.term = t.term
+           LEFT JOIN standard_word_roots o ON o.id = s.root_id
+           WHERE t.term <> '' AND s.root_id IS DISTINCT FROM r.id
+           GROUP BY 1, 2, 3, 4
+           ORDER BY 2, 1"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| SynonymClaim {
+            term: r.term,
+            namespace: r.namespace,
+            owner_root_id: r.owner_root_id,
+            owner_cn_name: r.owner_cn_name,
+            claimant_root_ids: r.claimant_root_ids,
+        })
+        .collect())
+}
+
+/// 将同义词归属到指定词根，并从同一命名空间的其他词根中移除，返回受影响的词根 ID
+pub async fn resolve(pool: &PgPool, term: &str, root_id: i32) -> Result<Vec<i32>, (StatusCode, String)> {
+    let db_error = |e: sqlx::Error| (StatusCode::INTERNAL_SERVER_ERROR, format!("数据库错误: {}", e));
+    let mut tx = pool.begin().await.map_err(db_error)?;
+
+    let root = sqlx::query!(
+        "SELECT cn_name, domain FROM standard_word_roots WHERE id = $1 FOR UPDATE",
+        root_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or((StatusCode::NOT_FOUND, "词根不存在".to_string()))?;
+    if root.cn_name == term {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "同义词不能与词根中文名相同".to_string()));
+    }
+    let ns = namespace(root.domain.as_deref());
+
+    let mut affected: Vec<i32> = sqlx::query_scalar!(
+        r#"UPDATE standard_word_roots
+           SET associated_terms = NULLIF(array_to_string(
+                   array_remove(regexp_split_to_array(COALESCE(associated_terms, ''), '[\s,，]+'), $1), ' '), '')
+           WHERE id <> $2 AND COALESCE(domain, '') = $3
+             AND $1 = ANY(regexp_split_to_array(COALESCE(associated_terms, ''), '[\s,，]+'))
+           RETURNING id"#,
+        term,
+        root_id,
+        ns
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    sqlx::query!(
+        r#"UPDATE standard_word_roots SET associated_terms = btrim(COALESCE(associated_terms, '') || ' ' || $1)
+           WHERE id = $2 AND NOT ($1 = ANY(regexp_split_to_array(COALESCE(associated_terms, ''), '[\s,，]+')))"#,
+        term,
+        root_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    sqlx::query!(
+        "INSERT INTO root_synonyms (root_id, term, namespace) VALUES ($1, $2, $3)
+         ON CONFLICT (namespace, term) DO UPDATE SET root_id = EXCLUDED.root_id",
+        root_id,
+        term,
+        ns
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    tx.commit().await.map_err(db_error)?;
+    affected.push(root_id);
+    Ok(affected)
+}
@@ -0,0 +1,390 @@
+use axum::http::StatusCode;
+use serde::Serialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+
+use crate::AppState;
+use crate::models::field::{CodeItem, StandardField};
+use crate::models::task::{NotificationTask, TASK_APPLIED, TASK_PENDING, TaskRequest};
+use crate::models::word_root::{WordRoot, normalize_terms};
+use crate::services::event_service::{ChangeAction, DomainEvent};
+use crate::services::{governance_service, pinyin_service, synonym_service, vector_service};
+
+/// 审批落库后的结果实体
+#[derive(Serialize)]
+#[serde(tag = "entity", content = "data", rename_all = "snake_case")]
+pub enum AppliedEntity {
+    Root(WordRoot),
+    Field(StandardField),
+    CodeItem(CodeItem),
+}
+
+type ApplyResult<T> = Result<T, (StatusCode, String)>;
+
+/// 将数据库错误转换为 HTTP 错误，唯一约束冲突返回 409
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    let is_conflict = e
+        .as_database_error()
+        .and_then(|d| d.code())
+        .is_some_and(|code| code == "23505");
+    if is_conflict {
+        (StatusCode::CONFLICT, format!("数据已存在: {}", e))
+    } else {
+        tracing::error!("!!! 审批落库失败: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("数据库错误: {}", e))
+    }
+}
+
+/// 浅合并：管理员在审批时补充/修正的属性覆盖用户原始提交
+fn merge_payload(mut payload: serde_json::Value, overrides: Option<serde_json::Value>) -> serde_json::Value {
+    if let (Some(base), Some(serde_json::Value::Object(extra))) = (payload.as_object_mut(), overrides) {
+        for (k, v) in extra {
+            base.insert(k, v);
+        }
+    }
+    payload
+}
+
+async fn ensure_field_exists(tx: &mut Transaction<'_, Postgres>, field_id: i32) -> ApplyResult<()> {
+    let exists = sqlx::query_scalar!("SELECT id FROM standard_fields WHERE id = $1", field_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(db_error)?;
+    match exists {
+        Some(_) => Ok(()),
+        None => Err((StatusCode::NOT_FOUND, format!("标准字段 ID={} 不存在", field_id))),
+    }
+}
+
+/// 替代字段必须存在且未被废弃
+async fn ensure_replacement(tx: &mut Transaction<'_, Postgres>, field_id: i32) -> ApplyResult<()> {
+    let deprecated = sqlx::query_scalar!(
+        r#"SELECT COALESCE(is_deprecated, false) as "deprecated!" FROM standard_fields WHERE id = $1"#,
+        field_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(db_error)?;
+    match deprecated {
+        Some(false) => Ok(()),
+        Some(true) => Err((StatusCode::UNPROCESSABLE_ENTITY, format!("替代字段 ID={} 已废弃", field_id))),
+        None => Err((StatusCode::NOT_FOUND, format!("替代字段 ID={} 不存在", field_id))),
+    }
+}
+
+/// 字段组成中的词根必须全部存在
+async fn ensure_roots_exist(tx: &mut Transaction<'_, Postgres>, root_ids: &[i32]) -> ApplyResult<()> {
+    let missing = sqlx::query_scalar!(
+        r#"SELECT t.id as "id!" FROM unnest($1::INT[]) AS t(id)
+           WHERE NOT EXISTS (SELECT 1 FROM standard_word_roots r WHERE r.id = t.id)"#,
+        root_ids
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(db_error)?;
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let ids: Vec<String> = missing.iter().map(|id| id.to_string()).collect();
+    Err((StatusCode::UNPROCESSABLE_ENTITY, format!("composition_ids 中的词根不存在: {}", ids.join(", "))))
+}
+
+/// 审批通过并直接将申请写入字典
+///
+/// 整个过程在一个事务内完成：写入词根/字段/代码值，并将任务标记为 APPLIED。
+/// 分词器与向量库属于外部状态，在事务提交后再同步。
+pub async fn apply_task(
+    state: &AppState,
+    task_id: i32,
+    overrides: Option<serde_json::Value>,
+) -> ApplyResult<AppliedEntity> {
+    let mut tx = state.db.begin().await.map_err(db_error)?;
+
+    let task = sqlx::query!(
+        "SELECT task_type, payload, status FROM notification_tasks WHERE id = $1 FOR UPDATE",
+        task_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or((StatusCode::NOT_FOUND, "任务不存在".to_string()))?;
+
+    if task.status != TASK_PENDING {
+        return Err((StatusCode::CONFLICT, format!("任务已处理，当前状态: {}", task.status)));
+    }
+
+    let payload = merge_payload(task.payload.unwrap_or_default(), overrides);
+    let mut request = TaskRequest::from_task(&task.task_type, payload)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    match &mut request {
+        TaskRequest::FieldRequest(p) => governance_service::normalize(&mut p.governance),
+        TaskRequest::FieldChange(p) => governance_service::normalize(&mut p.governance),
+        _ => {}
+    }
+    request
+        .validate()
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+
+    tracing::info!(">>> 审批落库: task_id={}, type={}", task_id, request.task_type());
+
+    let applied = match &request {
+        TaskRequest::FieldRequest(p) => {
+            let (Some(en_name), Some(ids)) = (&p.field_en_name, &p.composition_ids) else {
+                return Err((
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "请先补全 field_en_name 与 composition_ids 后再审批".to_string(),
+                ));
+            };
+            ensure_roots_exist(&mut tx, ids).await?;
+            let field = sqlx::query_as!(
+                StandardField,
+                r#"
+                INSERT INTO standard_fields (field_cn_name, field_en_name, composition_ids, data_type, associated_terms,
+                                             business_definition, calculation_rule, example_values, data_owner, data_steward, source_system)
+                VALUES ($1, $2, $3::INT[], $4, $5, NULLIF($6, ''), NULLIF($7, ''), COALESCE($8, '{}'::TEXT[]),
+                        NULLIF($9, ''), NULLIF($10, ''), NULLIF($11, ''))
+                RETURNING id, field_cn_name, field_en_name, composition_ids as "composition_ids!",
+                          data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at
+                "#,
+                p.field_cn_name, en_name, ids, p.data_type, p.associated_terms,
+                p.governance.business_definition, p.governance.calculation_rule, p.governance.example_values.as_deref(),
+                p.governance.data_owner, p.governance.data_steward, p.governance.source_system
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(db_error)?;
+            AppliedEntity::Field(field)
+        }
+        TaskRequest::RootRequest(p) => {
+            // 同义词位于申请的业务域对应的命名空间
+            let domain = p.domain.as_deref().map(str::trim).filter(|d| !d.is_empty());
+            let ns = synonym_service::namespace(domain);
+            let terms = synonym_service::split_terms(normalize_terms(p.associated_terms.clone()).as_deref());
+            let name_conflicts = synonym_service::find_name_conflicts(&mut tx, None, &ns, &p.cn_name)
+                .await
+                .map_err(db_error)?;
+            if let Some(c) = name_conflicts.first() {
+                return Err((
+                    StatusCode::CONFLICT,
+                    format!("中文名「{}」已是词根 {}({}) 的同义词，请在审批时修正 cn_name", c.term, c.cn_name, c.en_abbr),
+                ));
+            }
+            let conflicts = synonym_service::find_conflicts(&mut tx, None, &ns, &terms)
+                .await
+                .map_err(db_error)?;
+            if let Some(c) = conflicts.first() {
+                return Err((
+                    StatusCode::CONFLICT,
+                    format!("同义词「{}」已属于词根 {}({})，请在审批时修正 associated_terms", c.term, c.cn_name, c.en_abbr),
+                ));
+            }
+            let root = sqlx::query_as!(
+                WordRoot,
+                r#"
+                INSERT INTO standard_word_roots (cn_name, en_abbr, en_full_name, associated_terms, remark, domain)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id, cn_name, en_abbr, en_full_name, associated_terms, remark, domain, created_at, updated_at
+                "#,
+                p.cn_name, p.en_abbr, p.en_full_name,
+                synonym_service::accepted_terms(&terms, &[]), p.remark, domain
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(db_error)?;
+            synonym_service::assign(&mut tx, root.id, &ns, &terms).await.map_err(|e| {
+                if synonym_service::is_claimed(&e) {
+                    (StatusCode::CONFLICT, synonym_service::CLAIMED_MESSAGE.to_string())
+                } else {
+                    db_error(e)
+                }
+            })?;
+            AppliedEntity::Root(root)
+        }
+        TaskRequest::FieldChange(p) => {
+            if let Some(ids) = &p.composition_ids {
+                ensure_roots_exist(&mut tx, ids).await?;
+            }
+            let field = sqlx::query_as!(
+                StandardField,
+                r#"UPDATE standard_fields SET
+                     field_cn_name = COALESCE($1, field_cn_name),
+                     field_en_name = COALESCE($2, field_en_name),
+                     composition_ids = COALESCE($3::INT[], composition_ids),
+                     data_type = COALESCE($4, data_type),
+                     associated_terms = COALESCE($5, associated_terms),
+                     business_definition = NULLIF(COALESCE($7, business_definition), ''),
+                     calculation_rule = NULLIF(COALESCE($8, calculation_rule), ''),
+                     example_values = COALESCE($9::TEXT[], example_values),
+                     data_owner = NULLIF(COALESCE($10, data_owner), ''),
+                     data_steward = NULLIF(COALESCE($11, data_steward), ''),
+                     source_system = NULLIF(COALESCE($12, source_system), '')
+                   WHERE id = $6
+                   RETURNING id, field_cn_name, field_en_name, composition_ids as "composition_ids!",
+                             data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at"#,
+                p.field_cn_name, p.field_en_name, p.composition_ids.as_deref(),
+                p.data_type, p.associated_terms, p.field_id,
+                p.governance.business_definition, p.governance.calculation_rule, p.governance.example_values.as_deref(),
+                p.governance.data_owner, p.governance.data_steward, p.governance.source_system
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?
+            .ok_or((StatusCode::NOT_FOUND, format!("标准字段 ID={} 不存在", p.field_id)))?;
+            // 已审核的标准字段不能清空必填属性
+            let missing = if field.is_standard { governance_service::missing_attributes(&field) } else { vec![] };
+            if !missing.is_empty() {
+                return Err((
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    format!("标准字段不能清空必填属性: {}", missing.join(", ")),
+                ));
+            }
+            AppliedEntity::Field(field)
+        }
+        TaskRequest::FieldDeprecation(p) => {
+            if let Some(target) = p.replaced_by {
+                ensure_replacement(&mut tx, target).await?;
+            }
+            let field = sqlx::query_as!(
+                StandardField,
+                r#"UPDATE standard_fields SET is_deprecated = true, replaced_by = $1
+                   WHERE id = $2
+                   RETURNING id, field_cn_name, field_en_name, composition_ids as "composition_ids!",
+                             data_type, associated_terms, is_standard as "is_standard!", is_deprecated, replaced_by, domain, business_definition, calculation_rule, example_values, data_owner, data_steward, source_system, approved_by, approved_at, created_at, updated_at"#,
+                p.replaced_by, p.field_id
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?
+            .ok_or((StatusCode::NOT_FOUND, format!("标准字段 ID={} 不存在", p.field_id)))?;
+            AppliedEntity::Field(field)
+        }
+        TaskRequest::CodeValueRequest(p) => {
+            ensure_field_exists(&mut tx, p.field_id).await?;
+            let item = sqlx::query_as!(
+                CodeItem,
+                r#"INSERT INTO standard_code_items (field_id, code_value, code_name, remark)
+                   VALUES ($1, $2, $3, $4)
+                   RETURNING id, field_id, code_value, code_name, remark, created_at"#,
+                p.field_id, p.code_value, p.code_name, p.remark
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(db_error)?;
+            AppliedEntity::CodeItem(item)
+        }
+    };
+
+    // 记录最终落库的内容（含管理员补充的属性），便于追溯
+    sqlx::query!(
+        "UPDATE notification_tasks SET status = $1, is_read = true, resolved_at = NOW(), payload = $2 WHERE id = $3",
+        TASK_APPLIED,
+        request.payload_json(),
+        task_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    tx.commit().await.map_err(db_error)?;
+
+    // 事务提交后同步向量库，并通知订阅者（分词器由事件监听在后台同步）
+    let change_event = match &applied {
+        AppliedEntity::Root(root) => {
+            vector_service::upsert_root(state, root).await;
+            pinyin_service::refresh_roots(&state.db, &[root.id]).await;
+            DomainEvent::RootsChanged { action: ChangeAction::Created, ids: vec![root.id] }
+        }
+        AppliedEntity::Field(field) => {
+            vector_service::upsert_field(state, field).await;
+            pinyin_service::refresh_fields(&state.db, &[field.id]).await;
+            let action = match request {
+                TaskRequest::FieldRequest(_) => ChangeAction::Created,
+                _ => ChangeAction::Updated,
+            };
+            DomainEvent::FieldsChanged { action, ids: vec![field.id] }
+        }
+        AppliedEntity::CodeItem(item) => {
+            DomainEvent::FieldsChanged { action: ChangeAction::Updated, ids: vec![item.field_id] }
+        }
+    };
+    state.events.publish(DomainEvent::TaskResolved { task_ids: vec![task_id], status: TASK_APPLIED.to_string() }).await;
+    state.events.publish(change_event).await;
+
+    tracing::info!("<<< 审批落库完成: task_id={}", task_id);
+    Ok(applied)
+}
+
+/// 两个待处理字段申请的中文名 trigram 相似度达到该值即归为同一簇
+const CLUSTER_TRGM_THRESHOLD: f32 = 0.6;
+
+/// 一组内容近似的待处理字段申请
+#[derive(Serialize)]
+pub struct TaskCluster {
+    pub field_cn_name: String, // 代表名称（最早提交的申请）
+    pub tasks: Vec<NotificationTask>,
+}
+
+fn find_root(parent: &mut HashMap<i32, i32>, id: i32) -> i32 {
+    let p = *parent.get(&id).unwrap_or(&id);
+    if p == id {
+        return id;
+    }
+    let root = find_root(parent, p);
+    parent.insert(id, root);
+    root
+}
+
+/// 将近似的待处理字段申请聚类，方便管理员一次性处理
+///
+/// 相似对由 pg_trgm 在数据库中计算，再用并查集合并为连通分量；只返回包含多条申请的簇。
+pub async fn cluster_pending_field_requests(pool: &PgPool) -> Result<Vec<TaskCluster>, sqlx::Error> {
+    let tasks = sqlx::query_as!(
+        NotificationTask,
+        r#"SELECT id, task_type, payload as "payload!", status, is_read as "is_read!", requester_id, created_at as "created_at!", resolved_at
+           FROM notification_tasks
+           WHERE status = $1 AND task_type = 'FIELD_REQUEST'
+           ORDER BY created_at ASC"#,
+        TASK_PENDING
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let pairs = sqlx::query!(
+        r#"SELECT a.id as "a!", b.id as "b!"
+           FROM notification_tasks a
+           JOIN notification_tasks b ON a.id < b.id
+           WHERE a.status = $1 AND b.status = $1
+             AND a.task_type = 'FIELD_REQUEST' AND b.task_type = 'FIELD_REQUEST'
+             AND similarity(a.payload->>'field_cn_name', b.payload->>'field_cn_name') >= $2"#,
+        TASK_PENDING,
+        CLUSTER_TRGM_THRESHOLD
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut parent: HashMap<i32, i32> = HashMap::new();
+    for pair in pairs {
+        let (ra, rb) = (find_root(&mut parent, pair.a), find_root(&mut parent, pair.b));
+        if ra != rb {
+            // 以较早的申请（ID 较小）作为簇代表
+            parent.insert(ra.max(rb), ra.min(rb));
+        }
+    }
+
+    let mut groups: HashMap<i32, Vec<NotificationTask>> = HashMap::new();
+    for task in tasks {
+        let root = find_root(&mut parent, task.id);
+        groups.entry(root).or_default().push(task);
+    }
+
+    let mut clusters: Vec<TaskCluster> = groups
+        .into_values()
+        .filter(|tasks| tasks.len() > 1)
+        .map(|tasks| TaskCluster {
+            field_cn_name: tasks[0].payload["field_cn_name"].as_str().unwrap_or_default().to_string(),
+            tasks,
+        })
+        .collect();
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.tasks.len()));
+    Ok(clusters)
+}
@@ -0,0 +1,194 @@
+use jieba_rs::Jieba;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::time::Instant;
+use tokio::sync::Mutex;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::AppState;
+use crate::models::segment_dict::{RULE_FORCE_SPLIT, RULE_NEVER_SPLIT};
+use crate::services::event_service::DomainEvent;
+
+/// 词根中文名与同义词注入分词器时使用的词频
+const ROOT_WORD_FREQ: usize = 99999;
+
+/// 当前分词器中已注入的词条；同时作为同步锁，保证重建与增量更新串行执行
+static INJECTED: Lazy<Mutex<Injected>> = Lazy::new(|| Mutex::new(Injected::default()));
+
+#[derive(Default)]
+struct Injected {
+    ready: bool,                   // 是否已完成过一次全量构建
+    root_terms: HashSet<String>,   // 来自词根中文名与同义词的词条
+    custom_words: HashSet<String>, // 自定义词典中的词（其词频优先于词根）
+    custom: Vec<CustomWord>,       // 注入时的自定义词典，用于判断词典是否有变更
+}
+
+/// 同步方式
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMode {
+    Full,        // 从内置词典重新构建
+    Incremental, // 只追加新增的词条
+    Unchanged,   // 词条无变化
+}
+
+/// 同步结果
+#[derive(Debug, Serialize)]
+pub struct RebuildStats {
+    pub mode: SyncMode,
+    pub root_words: usize,   // 来自词根中文名与同义词的词条数
+    pub custom_words: usize, // 自定义词典词条数
+    pub added: usize,        // 本次新增的词根词条
+    pub removed: usize,      // 本次移除的词根词条
+    pub elapsed_ms: u128,
+}
+
+#[derive(Clone, PartialEq)]
+struct CustomWord {
+    word: String,
+    freq: Option<i32>,
+    tag: Option<String>,
+    rule: String,
+}
+
+/// 当前全部词根的中文名与同义词（同义词按空白与逗号拆分）
+async fn load_root_terms(pool: &PgPool) -> Result<HashSet<String>, String> {
+    let terms = sqlx::query_scalar!(
+        r#"SELECT DISTINCT t.term as "term!"
+           FROM standard_word_roots r,
+             unnest(array_prepend(r.cn_name, regexp_split_to_array(COALESCE(r.associated_terms, ''), '[\s,，]+'))) AS t(term)
+           WHERE t.term <> ''"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("词根词条加载失败: {}", e))?;
+    Ok(terms.into_iter().collect())
+}
+
+/// 当前自定义词典，按 ID 排序
+async fn load_custom(pool: &PgPool) -> Result<Vec<CustomWord>, String> {
+    sqlx::query_as!(CustomWord, "SELECT word, freq, tag, rule FROM segment_dictionary ORDER BY id")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("自定义词典加载失败: {}", e))
+}
+
+/// 以内置词典为基础，依次注入词根中文名与同义词、自定义词典，构建新的分词器后整体替换全局 `JIEBA`
+///
+/// 自定义词典最后注入，可覆盖词根的词频：`never_split` 的词频不低于其被拆开时的估算值，
+/// `force_split` 的词频置 0，使其不再作为一个词出现。构建在阻塞线程中完成，替换时才持有写锁。
+async fn full_rebuild(
+    injected: &mut Injected,
+    root_terms: HashSet<String>,
+    custom: Vec<CustomWord>,
+    started: Instant,
+) -> Result<RebuildStats, String> {
+    let added = root_terms.difference(&injected.root_terms).count();
+    let removed = injected.root_terms.difference(&root_terms).count();
+    let custom_words: HashSet<String> = custom.iter().map(|c| c.word.clone()).collect();
+
+    let terms = root_terms.clone();
+    let entries = custom.clone();
+    let jieba = tokio::task::spawn_blocking(move || {
+        let mut jieba = Jieba::new();
+        for word in &terms {
+            jieba.add_word(word, Some(ROOT_WORD_FREQ), None);
+        }
+        for c in &entries {
+            let freq = match c.rule.as_str() {
+                RULE_FORCE_SPLIT => Some(0),
+                RULE_NEVER_SPLIT => {
+                    let given = c.freq.map_or(ROOT_WORD_FREQ, |f| f.max(1) as usize);
+                    Some(given.max(jieba.suggest_freq(&c.word)))
+                }
+                _ => c.freq.map(|f| f.max(1) as usize),
+            };
+            jieba.add_word(&c.word, freq, c.tag.as_deref());
+        }
+        jieba
+    })
+    .await
+    .map_err(|e| format!("分词器构建失败: {}", e))?;
+
+    *crate::JIEBA.write().await = jieba;
+    *injected = Injected { ready: true, root_terms, custom_words, custom };
+    Ok(RebuildStats {
+        mode: SyncMode::Full,
+        root_words: injected.root_terms.len(),
+        custom_words: injected.custom_words.len(),
+        added,
+        removed,
+        elapsed_ms: started.elapsed().as_millis(),
+    })
+}
+
+/// 全量重建分词器（启动与管理员手动重载时使用）
+pub async fn rebuild(pool: &PgPool) -> Result<RebuildStats, String> {
+    let started = Instant::now();
+    let mut injected = INJECTED.lock().await;
+    let root_terms = load_root_terms(pool).await?;
+    let custom = load_custom(pool).await?;
+    full_rebuild(&mut injected, root_terms, custom, started).await
+}
+
+/// 按当前词根集合与自定义词典同步分词器
+///
+/// 只有新增词根词条时直接追加（自定义词典中已有的词保持其设置）；有词条被移除或自定义词典有变更时全量重建，
+/// 因为分词器无法恢复被覆盖前的内置词频。
+pub async fn sync(pool: &PgPool) -> Result<RebuildStats, String> {
+    let started = Instant::now();
+    let mut injected = INJECTED.lock().await;
+    let root_terms = load_root_terms(pool).await?;
+    let custom = load_custom(pool).await?;
+    if !injected.ready || injected.custom != custom || injected.root_terms.difference(&root_terms).next().is_some() {
+        return full_rebuild(&mut injected, root_terms, custom, started).await;
+    }
+
+    let added: Vec<String> = root_terms.difference(&injected.root_terms).cloned().collect();
+    if !added.is_empty() {
+        let mut jieba_write = crate::JIEBA.write().await;
+        for word in added.iter().filter(|w| !injected.custom_words.contains(*w)) {
+            jieba_write.add_word(word, Some(ROOT_WORD_FREQ), None);
+        }
+    }
+    let mode = if added.is_empty() { SyncMode::Unchanged } else { SyncMode::Incremental };
+    injected.root_terms.extend(added.iter().cloned());
+    Ok(RebuildStats {
+        mode,
+        root_words: injected.root_terms.len(),
+        custom_words: injected.custom_words.len(),
+        added: added.len(),
+        removed: 0,
+        elapsed_ms: started.elapsed().as_millis(),
+    })
+}
+
+/// 按事件同步，失败只记录日志（下次同步或重载时补齐）
+pub async fn refresh(pool: &PgPool) {
+    match sync(pool).await {
+        Ok(stats) => tracing::debug!("分词器已同步: {:?}", stats),
+        Err(e) => tracing::warn!("--- 分词器同步失败: {}", e),
+    }
+}
+
+/// 订阅词根与自定义词典变更事件并同步分词器
+///
+/// 分词器不在请求中更新：写入词根或词典的副本与其他副本（开启 `EVENTS_PG_NOTIFY` 时）都由此在后台同步，
+/// 需要全量重建时构建在阻塞线程中完成。已是最新时同步不做任何事，事件积压丢失时按当前数据同步一次。
+pub fn spawn_listener(state: &AppState) {
+    let pool = state.db.clone();
+    let mut rx = state.events.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(DomainEvent::RootsChanged { .. } | DomainEvent::SegmentDictChanged { .. }) | Err(RecvError::Lagged(_)) => {
+                    refresh(&pool).await
+                }
+                Ok(_) => {}
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
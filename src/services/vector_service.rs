@@ -0,0 +1,122 @@
+use crate::AppState;
+use crate::models::field::StandardField;
+use crate::models::word_root::WordRoot;
+use qdrant_client::qdrant::point_id::PointIdOptions;
+use qdrant_client::qdrant::{PointStruct, ScoredPoint, SearchBatchPointsBuilder, SearchPointsBuilder, UpsertPointsBuilder, Value};
+use std::collections::HashMap;
+
+/// 词根向量化文本：中文名 + 英文全称 + 同义词
+pub fn root_embed_text(root: &WordRoot) -> String {
+    format!(
+        "{} {} {}",
+        root.cn_name,
+        root.en_full_name.as_deref().unwrap_or(""),
+        root.associated_terms.as_deref().unwrap_or("")
+    )
+}
+
+/// 标准字段向量化文本：中文名 + 同义词 + 业务定义
+pub fn field_embed_text(field: &StandardField) -> String {
+    format!(
+        "{} {} {}",
+        field.field_cn_name,
+        field.associated_terms.as_deref().unwrap_or(""),
+        field.business_definition.as_deref().unwrap_or("")
+    )
+}
+
+pub fn root_payload(root: &WordRoot) -> HashMap<String, Value> {
+    let mut payload_map: HashMap<String, Value> = HashMap::new();
+    payload_map.insert("cn_name".to_string(), root.cn_name.clone().into());
+    payload_map.insert("en_abbr".to_string(), root.en_abbr.clone().into());
+    payload_map
+}
+
+pub fn field_payload(field: &StandardField) -> HashMap<String, Value> {
+    let mut payload_map: HashMap<String, Value> = HashMap::new();
+    payload_map.insert("cn_name".to_string(), field.field_cn_name.clone().into());
+    payload_map.insert("en_name".to_string(), field.field_en_name.clone().into());
+    payload_map
+}
+
+/// 批量计算向量
+///
+/// 模型推理耗时较长，放到阻塞线程池中执行，避免占用 tokio 工作线程
+pub async fn embed(state: &AppState, texts: Vec<String>) -> Result<Vec<Vec<f32>>, String> {
+    let model = state.embed_model.clone();
+    tokio::task::spawn_blocking(move || model.lock().embed(texts, None))
+        .await
+        .map_err(|e| format!("向量计算任务异常: {}", e))?
+        .map_err(|e| format!("向量计算失败: {}", e))
+}
+
+/// 计算单条词根向量并写入 Qdrant `word_roots` 集合
+pub async fn upsert_root(state: &AppState, root: &WordRoot) {
+    if let Ok(embeddings) = embed(state, vec![root_embed_text(root)]).await {
+        let point = PointStruct::new(root.id as u64, embeddings[0].clone(), root_payload(root));
+        let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("word_roots", vec![point])).await;
+    }
+}
+
+/// 计算单条标准字段向量并写入 Qdrant `standard_fields` 集合
+pub async fn upsert_field(state: &AppState, field: &StandardField) {
+    if let Ok(embeddings) = embed(state, vec![field_embed_text(field)]).await {
+        let point = PointStruct::new(field.id as u64, embeddings[0].clone(), field_payload(field));
+        let _ = state.qdrant.upsert_points(UpsertPointsBuilder::new("standard_fields", vec![point])).await;
+        tracing::info!("<<< 向量库同步完成: ID={}", field.id);
+    }
+}
+
+/// 语义检索：对文本向量化后在指定集合中检索，返回 (数据库 ID, 相似度) 列表
+pub async fn search(
+    state: &AppState,
+    collection: &str,
+    text: &str,
+    limit: u64,
+) -> Result<Vec<(i32, f32)>, String> {
+    let embeddings = embed(state, vec![text.to_string()]).await?;
+
+    let res = state
+        .qdrant
+        .search_points(SearchPointsBuilder::new(collection, embeddings[0].clone(), limit))
+        .await
+        .map_err(|e| format!("向量库检索失败: {}", e))?;
+
+    Ok(to_hits(res.result))
+}
+
+/// 批量语义检索：一次向量化、一次批量查询，结果与 `texts` 一一对应，只保留相似度不低于 `min_score` 的命中
+pub async fn search_many(
+    state: &AppState,
+    collection: &str,
+    texts: &[String],
+    limit: u64,
+    min_score: f32,
+) -> Result<Vec<Vec<(i32, f32)>>, String> {
+    if texts.is_empty() {
+        return Ok(vec![]);
+    }
+    let embeddings = embed(state, texts.to_vec()).await?;
+
+    let searches: Vec<_> = embeddings
+        .into_iter()
+        .map(|v| SearchPointsBuilder::new(collection, v, limit).score_threshold(min_score).build())
+        .collect();
+    let res = state
+        .qdrant
+        .search_batch_points(SearchBatchPointsBuilder::new(collection, searches))
+        .await
+        .map_err(|e| format!("向量库检索失败: {}", e))?;
+
+    Ok(res.result.into_iter().map(|r| to_hits(r.result)).collect())
+}
+
+fn to_hits(points: Vec<ScoredPoint>) -> Vec<(i32, f32)> {
+    points
+        .into_iter()
+        .filter_map(|p| match p.id?.point_id_options? {
+            PointIdOptions::Num(n) => Some((n as i32, p.score)),
+            PointIdOptions::Uuid(_) => None,
+        })
+        .collect()
+}
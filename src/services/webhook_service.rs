@@ -0,0 +1,245 @@
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use sha2::Sha256;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::models::webhook::{
+    DELIVERY_FAILED, DELIVERY_PENDING, DELIVERY_SUCCEEDED, WebhookDelivery, WebhookSubscription,
+};
+use crate::AppState;
+use crate::services::event_service::DomainEvent;
+
+/// 单条投递的最大尝试次数，超过后标记为 FAILED
+const MAX_ATTEMPTS: i32 = 8;
+/// 指数退避：第 n 次失败后等待 BASE * 2^(n-1) 秒，最长 MAX 秒
+const BASE_BACKOFF_SECS: f64 = 30.0;
+const MAX_BACKOFF_SECS: f64 = 3600.0;
+/// 队列轮询间隔与每轮最多投递数量
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const BATCH_SIZE: usize = 20;
+/// 单次 HTTP 请求超时
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+/// 领取后的租约时长：投递进程异常退出时，超过租约的任务会被重新领取。
+/// 每次只领取一条，租约只需覆盖单次请求超时
+const LEASE_SECS: f64 = 60.0;
+
+static HTTP: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(SEND_TIMEOUT)
+        .build()
+        .expect("无法创建 Webhook HTTP 客户端")
+});
+
+/// 计算签名：hex(HMAC-SHA256(secret, "{timestamp}.{body}"))
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC 接受任意长度密钥");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 将事件写入所有匹配订阅的投递队列
+async fn enqueue(pool: &PgPool, event: &DomainEvent) {
+    let data = match serde_json::to_value(event) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::warn!("--- Webhook 事件序列化失败: {}", e);
+            return;
+        }
+    };
+
+    let res = sqlx::query!(
+        "INSERT INTO webhook_deliveries (subscription_id, event_type, payload)
+         SELECT id, $1::TEXT, $2 FROM webhook_subscriptions
+         WHERE is_active AND (cardinality(event_types) = 0 OR $1::TEXT = ANY(event_types))",
+        event.name(),
+        data
+    )
+    .execute(pool)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() > 0 => {
+            tracing::debug!("Webhook 入队: event={}, 订阅数={}", event.name(), r.rows_affected())
+        }
+        Ok(_) => {}
+        Err(e) => tracing::error!("!!! Webhook 入队失败: {}", e),
+    }
+}
+
+/// 订阅本副本发布的事件并写入投递队列
+///
+/// 只处理本副本发布的事件，避免多副本通过 NOTIFY 收到同一事件后重复入队；入队不占用发布方的请求路径。
+pub fn spawn_enqueuer(state: &AppState) {
+    let pool = state.db.clone();
+    let mut rx = state.events.subscribe_local();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => enqueue(&pool, &event).await,
+                Err(RecvError::Lagged(n)) => tracing::error!("!!! Webhook 入队积压，丢失 {} 个事件", n),
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+/// 发送一次 HTTP 请求，返回响应状态码；网络错误返回 Err
+async fn send(
+    url: &str,
+    secret: &str,
+    delivery_id: i64,
+    event_type: &str,
+    payload: &serde_json::Value,
+    created_at: chrono::DateTime<chrono::Utc>,
+) -> Result<u16, String> {
+    let body = serde_json::json!({
+        "id": delivery_id,
+        "event": event_type,
+        "created_at": created_at,
+        "data": payload,
+    })
+    .to_string();
+    let timestamp = chrono::Utc::now().timestamp();
+
+    HTTP.post(url)
+        .header("Content-Type", "application/json")
+        .header("X-Dict-Event", event_type)
+        .header("X-Dict-Delivery", delivery_id.to_string())
+        .header("X-Dict-Timestamp", timestamp.to_string())
+        .header("X-Dict-Signature", format!("sha256={}", sign(secret, timestamp, &body)))
+        .body(body)
+        .send()
+        .await
+        .map(|resp| resp.status().as_u16())
+        .map_err(|e| e.to_string())
+}
+
+fn backoff_secs(attempts: i32) -> f64 {
+    (BASE_BACKOFF_SECS * 2f64.powi(attempts.max(1) - 1)).min(MAX_BACKOFF_SECS)
+}
+
+/// 记录一次投递结果；失败时按指数退避安排重试（`retry = false` 时直接标记失败）
+async fn record_attempt(
+    pool: &PgPool,
+    delivery_id: i64,
+    attempts: i32,
+    outcome: &Result<u16, String>,
+    retry: bool,
+) -> Result<(), sqlx::Error> {
+    if let Ok(code) = outcome
+        && (200..300).contains(code)
+    {
+        sqlx::query!(
+            "UPDATE webhook_deliveries
+             SET status = $1, attempts = $2, last_status_code = $3, last_error = NULL, delivered_at = NOW()
+             WHERE id = $4",
+            DELIVERY_SUCCEEDED,
+            attempts,
+            *code as i32,
+            delivery_id
+        )
+        .execute(pool)
+        .await?;
+        return Ok(());
+    }
+
+    let (code, error) = match outcome {
+        Ok(code) => (Some(*code as i32), format!("HTTP {}", code)),
+        Err(e) => (None, e.clone()),
+    };
+    let status = if retry && attempts < MAX_ATTEMPTS { DELIVERY_PENDING } else { DELIVERY_FAILED };
+    tracing::warn!("--- Webhook 投递失败: id={}, 第 {} 次, {}", delivery_id, attempts, error);
+
+    sqlx::query!(
+        "UPDATE webhook_deliveries
+         SET status = $1, attempts = $2, last_status_code = $3, last_error = $4,
+             next_attempt_at = NOW() + make_interval(secs => $5)
+         WHERE id = $6",
+        status,
+        attempts,
+        code,
+        error,
+        backoff_secs(attempts),
+        delivery_id
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 逐条领取到期的投递并发送，每轮最多 `BATCH_SIZE` 条
+///
+/// 使用 `FOR UPDATE SKIP LOCKED` 领取并顺延 `next_attempt_at` 作为租约，多副本可同时运行而不会重复投递。
+/// 每次只领取一条，避免同批后续任务在等待前序请求超时期间租约过期被其他副本重复领取。
+async fn process_due(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let mut sent = 0;
+    while sent < BATCH_SIZE {
+        let due = sqlx::query!(
+            r#"UPDATE webhook_deliveries d SET next_attempt_at = NOW() + make_interval(secs => $1)
+               FROM webhook_subscriptions s
+               WHERE d.subscription_id = s.id AND d.id = (
+                   SELECT q.id FROM webhook_deliveries q
+                   JOIN webhook_subscriptions qs ON qs.id = q.subscription_id
+                   WHERE qs.is_active AND q.status = $2 AND q.next_attempt_at <= NOW()
+                   ORDER BY q.next_attempt_at
+                   LIMIT 1
+                   FOR UPDATE OF q SKIP LOCKED)
+               RETURNING d.id, d.event_type, d.payload, d.attempts, d.created_at, s.url, s.secret"#,
+            LEASE_SECS,
+            DELIVERY_PENDING
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        let Some(d) = due else { break };
+        let outcome = send(&d.url, &d.secret, d.id, &d.event_type, &d.payload, d.created_at).await;
+        record_attempt(pool, d.id, d.attempts + 1, &outcome, true).await?;
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+/// 启动后台投递任务
+pub fn spawn_worker(pool: PgPool) {
+    tokio::spawn(async move {
+        tracing::info!("Webhook 投递任务已启动");
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match process_due(&pool).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("<<< Webhook 本轮投递 {} 条", n),
+                Err(e) => tracing::error!("!!! Webhook 投递队列处理异常: {}", e),
+            }
+        }
+    });
+}
+
+/// 测试投递：立即向订阅地址发送一条 `ping` 事件并返回投递记录（失败不重试）
+pub async fn test_fire(pool: &PgPool, sub: &WebhookSubscription) -> Result<WebhookDelivery, sqlx::Error> {
+    let payload = serde_json::json!({ "type": "ping", "subscription_id": sub.id, "name": sub.name });
+    let delivery = sqlx::query!(
+        "INSERT INTO webhook_deliveries (subscription_id, event_type, payload, status)
+         VALUES ($1, 'ping', $2, $3) RETURNING id, created_at",
+        sub.id,
+        payload,
+        DELIVERY_FAILED // 先标记为终态，避免被后台任务重复领取
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let outcome = send(&sub.url, &sub.secret, delivery.id, "ping", &payload, delivery.created_at).await;
+    record_attempt(pool, delivery.id, 1, &outcome, false).await?;
+
+    sqlx::query_as!(
+        WebhookDelivery,
+        "SELECT id, subscription_id, event_type, payload, status, attempts, next_attempt_at,
+                last_status_code, last_error, created_at, delivered_at
+         FROM webhook_deliveries WHERE id = $1",
+        delivery.id
+    )
+    .fetch_one(pool)
+    .await
+}